@@ -1,13 +1,18 @@
+use crate::currency::{BalanceOf, GovernanceCurrency};
+use crate::traits::{IsMutualFollower, TransactionBaseFee};
 use rstd::prelude::*;
-use parity_codec::Codec;
+use rstd::collections::btree_set::BTreeSet;
+use parity_codec::{Codec, Decode};
 use parity_codec_derive::{Encode, Decode};
-use srml_support::{StorageMap, StorageValue, decl_module, decl_storage, decl_event, dispatch, ensure, fail, Parameter};
-use runtime_primitives::traits::{SimpleArithmetic, As, Member, MaybeDebug, MaybeSerializeDebug};
+use srml_support::traits::{Currency, ReservableCurrency};
+use srml_support::{storage, StorageMap, StorageValue, decl_module, decl_storage, decl_event, dispatch, ensure, fail, Parameter};
+use runtime_primitives::traits::{SimpleArithmetic, As, Dispatchable, Hash, Member, MaybeDebug, MaybeSerializeDebug, Zero};
 use system::{self, ensure_signed};
 use runtime_io::print;
+use substrate_client::decl_runtime_apis;
 use {timestamp};
 
-pub trait Trait: system::Trait + timestamp::Trait + MaybeDebug {
+pub trait Trait: system::Trait + timestamp::Trait + GovernanceCurrency + MaybeDebug {
 
   type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
 
@@ -22,6 +27,11 @@ pub trait Trait: system::Trait + timestamp::Trait + MaybeDebug {
 
   type ReactionId: Parameter + Member + SimpleArithmetic + Codec + Default + Copy
     + As<usize> + As<u64> + MaybeSerializeDebug + PartialEq;
+
+  type BlogListId: Parameter + Member + SimpleArithmetic + Codec + Default + Copy
+    + As<usize> + As<u64> + MaybeSerializeDebug + PartialEq;
+
+  type TransactionFees: TransactionBaseFee<Self>;
 }
 
 #[cfg_attr(feature = "std", derive(Debug))]
@@ -46,7 +56,53 @@ pub struct Blog<T: Trait> {
   slug: Vec<u8>,
   json: Vec<u8>,
 
-  posts_count: u16,
+  posts_count: u32,
+
+  // Can be set by the verification authority only:
+  verified: Option<Change<T>>,
+
+  // Can be updated by the owner. Who can post channel messages, if the blog has a channel:
+  channel: Option<ChannelPosters>,
+
+  // Can be updated by the owner:
+  status: BlogStatus,
+
+  // ISO 639-1 code of the content language. Can be updated by the owner:
+  language: Option<[u8; 2]>,
+
+  // Sensitive content. Can be set by the owner, but cleared only by root if root has set it:
+  nsfw: bool,
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq)]
+pub enum BlogStatus {
+    Active,
+    // A frozen blog: it is readable, but its content cannot be created, changed, reacted to or followed.
+    Archived,
+}
+
+impl Default for BlogStatus {
+    fn default() -> Self {
+        BlogStatus::Active
+    }
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq)]
+pub enum ChannelPosters {
+    // Any follower of a blog, including its writers and the owner.
+    Followers,
+    // Writers of a blog and accounts with a role in this blog, including the owner.
+    Writers,
+}
+
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Encode, Decode, PartialEq)]
+pub struct ChannelMessage<T: Trait> {
+  id: u64,
+  created: Change<T>,
+  ipfs_hash: Vec<u8>,
 }
 
 #[cfg_attr(feature = "std", derive(Debug))]
@@ -55,6 +111,7 @@ pub struct BlogUpdate<T: Trait> {
   writers: Option<Vec<T::AccountId>>,
   slug: Option<Vec<u8>>,
   json: Option<Vec<u8>>,
+  language: Option<Option<[u8; 2]>>,
 }
 
 #[cfg_attr(feature = "std", derive(Debug))]
@@ -71,9 +128,91 @@ pub struct Post<T: Trait> {
   slug: Vec<u8>,
   json: Vec<u8>,
 
-  comments_count: u16,
-  upvotes_count: u16,
-  downvotes_count: u16,
+  comments_count: u32,
+  upvotes_count: u32,
+  downvotes_count: u32,
+
+  // Comments of a post are split into pages of COMMENTS_PAGE_SIZE comments:
+  comment_pages_count: u32,
+
+  // Sums of reactions on all comments of this post:
+  comments_upvotes_count: u32,
+  comments_downvotes_count: u32,
+
+  // Updated by the views oracle only:
+  views_count: u64,
+
+  // Can be set by the owner or a blog owner:
+  hidden: bool,
+
+  // Promoted content. Set on creation for a fee and cannot be removed:
+  is_sponsored: bool,
+
+  // ISO 639-1 code of the content language:
+  language: Option<[u8; 2]>,
+
+  // Can be set by the owner, but only to a more permissive license:
+  license: Option<ContentLicense>,
+
+  // Until this block only blog members can comment on and react to this post:
+  public_after: Option<T::BlockNumber>,
+
+  // Can be changed by the owner, but only to a stricter permission:
+  comment_permission: CommentPermission,
+
+  // Sensitive content. Can be set by the owner, but cleared only by root if root has set it:
+  nsfw: bool,
+}
+
+// A post without its JSON, so that lists of posts are cheap to return from the runtime API.
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Encode, Decode, PartialEq)]
+pub struct PostSummary<T: Trait> {
+  id: T::PostId,
+  blog_id: T::BlogId,
+  created: Change<T>,
+  updated: Option<Change<T>>,
+  slug: Vec<u8>,
+  comments_count: u32,
+  upvotes_count: u32,
+  downvotes_count: u32,
+  comment_pages_count: u32,
+  comments_upvotes_count: u32,
+  comments_downvotes_count: u32,
+  views_count: u64,
+  hidden: bool,
+  is_sponsored: bool,
+  language: Option<[u8; 2]>,
+  license: Option<ContentLicense>,
+  public_after: Option<T::BlockNumber>,
+  comment_permission: CommentPermission,
+  nsfw: bool,
+}
+
+impl<T: Trait> From<Post<T>> for PostSummary<T> {
+  fn from(post: Post<T>) -> Self {
+    PostSummary {
+      id: post.id,
+      blog_id: post.blog_id,
+      created: post.created,
+      updated: post.updated,
+      slug: post.slug,
+      comments_count: post.comments_count,
+      upvotes_count: post.upvotes_count,
+      downvotes_count: post.downvotes_count,
+      comment_pages_count: post.comment_pages_count,
+      comments_upvotes_count: post.comments_upvotes_count,
+      comments_downvotes_count: post.comments_downvotes_count,
+      views_count: post.views_count,
+      hidden: post.hidden,
+      is_sponsored: post.is_sponsored,
+      language: post.language,
+      license: post.license,
+      public_after: post.public_after,
+      comment_permission: post.comment_permission,
+      nsfw: post.nsfw,
+    }
+  }
 }
 
 #[cfg_attr(feature = "std", derive(Debug))]
@@ -82,6 +221,25 @@ pub struct PostUpdate<T: Trait> {
   blog_id: Option<T::BlogId>,
   slug: Option<Vec<u8>>,
   json: Option<Vec<u8>>,
+  language: Option<Option<[u8; 2]>>,
+  license: Option<ContentLicense>,
+  comment_permission: Option<CommentPermission>,
+  links_to: Option<Vec<T::PostId>>,
+}
+
+// Optional settings of a new post. New settings go here rather than into create_post() params.
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Encode, Decode, PartialEq)]
+pub struct PostOptions<T: Trait> {
+  mentioned_accounts: Vec<T::AccountId>,
+  is_sponsored: bool,
+  language: Option<[u8; 2]>,
+  // Publish on behalf of the blog, see WriterByPostId:
+  post_as_blog: bool,
+  license: Option<ContentLicense>,
+  public_after: Option<T::BlockNumber>,
+  comment_permission: CommentPermission,
+  links_to: Vec<T::PostId>,
 }
 
 #[cfg_attr(feature = "std", derive(Debug))]
@@ -96,8 +254,20 @@ pub struct Comment<T: Trait> {
   // Can be updated by the owner:
   json: Vec<u8>,
 
-  upvotes_count: u16,
-  downvotes_count: u16,
+  upvotes_count: u32,
+  downvotes_count: u32,
+  edits_count: u16,
+
+  // Number of all replies in the subtree of this comment, up to MaxReplyDepthToCount levels deep:
+  replies_total_count: u32,
+
+  // Can be set by the owner, a blog owner or a blog moderator:
+  hidden: bool,
+
+  // Badges of the comment author in the thread, as of the comment creation:
+  is_post_author: bool,
+  is_blog_owner: bool,
+  is_blog_writer: bool,
 }
 
 #[cfg_attr(feature = "std", derive(Debug))]
@@ -106,11 +276,225 @@ pub struct CommentUpdate {
   json: Vec<u8>,
 }
 
+// A kind of paid membership defined by a blog owner.
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Encode, Decode, PartialEq)]
+pub struct MembershipTier<T: Trait> {
+  name: Vec<u8>,
+  price: BalanceOf<T>,
+  // For how many blocks a membership lasts:
+  duration: T::BlockNumber,
+}
+
+// An org has its own account that acts in this module, e.g. to create and manage blogs of the org.
+// A call is dispatched as the org account once the threshold number of org members approve it.
+// Members are added and removed, and the threshold is changed, by such calls too.
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Encode, Decode, PartialEq)]
+pub struct Org<T: Trait> {
+  id: u64,
+  created: Change<T>,
+  account: T::AccountId,
+  members: Vec<T::AccountId>,
+  threshold: u16,
+}
+
+// A call of this module that waits for approvals of org members.
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Encode, Decode, PartialEq)]
+pub struct OrgProposal<T: Trait> {
+  id: u64,
+  org_id: u64,
+  created: Change<T>,
+  call: Call<T>,
+  approvals: Vec<T::AccountId>,
+}
+
+// A membership of an account in a blog. It can be transferred to another account.
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Encode, Decode, PartialEq)]
+pub struct BlogMembership<T: Trait> {
+  id: u64,
+  blog_id: T::BlogId,
+  // An index of a tier in the tiers of the blog:
+  tier: u16,
+  owner: T::AccountId,
+  expires_at: T::BlockNumber,
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq)]
+pub enum AppealStatus {
+    Open,
+    // Root agreed with the appeal and the bond has been returned.
+    Refunded,
+    // Root rejected the appeal and the bond has been slashed.
+    Slashed,
+}
+
+// An appeal of a comment author against a blog moderator who hid their comment.
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Encode, Decode, PartialEq)]
+pub struct Appeal<T: Trait> {
+  created: Change<T>,
+  bond: BalanceOf<T>,
+  status: AppealStatus,
+}
+
+// A post that any account can submit to a blog. It gets published once the blog owner approves it.
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Encode, Decode, PartialEq)]
+pub struct PostSubmission<T: Trait> {
+  id: u64,
+  blog_id: T::BlogId,
+  created: Change<T>,
+  slug: Vec<u8>,
+  json: Vec<u8>,
+  deposit: BalanceOf<T>,
+  expires_at: T::BlockNumber,
+}
+
+// A posting fee that stays reserved from a writer until a blog owner accepts the post or the hold period ends.
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Encode, Decode, PartialEq)]
+pub struct PostingFee<T: Trait> {
+  payer: T::AccountId,
+  // A blog where the post was paid for, even if the post has moved since:
+  blog_id: T::BlogId,
+  amount: BalanceOf<T>,
+  release_at: T::BlockNumber,
+}
+
+// An ordered collection of posts of the same blog.
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Encode, Decode, PartialEq)]
+pub struct PostSeries<T: Trait> {
+  id: u64,
+  blog_id: T::BlogId,
+  created: Change<T>,
+  updated: Option<Change<T>>,
+  ipfs_hash: Vec<u8>,
+}
+
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Encode, Decode, PartialEq)]
+pub struct BlogList<T: Trait> {
+  id: T::BlogListId,
+  created: Change<T>,
+  updated: Option<Change<T>>,
+
+  // Can be updated by the owner:
+  name: Vec<u8>,
+  is_public: bool,
+
+  blogs_count: u16,
+}
+
+// Limits that a blog gets once it has at least min_followers followers.
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Encode, Decode, PartialEq)]
+pub struct FollowerTier<T: Trait> {
+  min_followers: u32,
+  max_custom_reactions: u32,
+  min_blocks_between_posts: T::BlockNumber,
+}
+
+// A comment under a post of an account or a reply to a comment of an account.
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Encode, Decode, PartialEq)]
+pub struct Reply<T: Trait> {
+  // A sequential number of this reply among all replies to the account:
+  index: u64,
+  comment_id: T::CommentId,
+  actor: T::AccountId,
+}
+
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Encode, Decode, PartialEq)]
+pub enum Mention<T: Trait> {
+  Post(T::PostId),
+  Comment(T::CommentId),
+}
+
+// Who can comment on a post.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq)]
+pub enum CommentPermission {
+    Everyone,
+    BlogFollowers,
+    Nobody,
+}
+
+impl CommentPermission {
+    // The greater, the stricter. A permission can only be changed to a stricter one,
+    // so that it cannot be loosened after a discussion has started.
+    fn strictness(&self) -> u8 {
+        match self {
+            CommentPermission::Everyone => 0,
+            CommentPermission::BlogFollowers => 1,
+            CommentPermission::Nobody => 2,
+        }
+    }
+}
+
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Encode, Decode, PartialEq, Eq)]
+pub enum ContentLicense {
+    Cc0,
+    CcBy,
+    CcBySa,
+    AllRightsReserved,
+    // An IPFS hash of a custom license text.
+    Custom(Vec<u8>),
+}
+
+impl ContentLicense {
+    // The greater, the more permissive. A license can only be changed to a more permissive one,
+    // because a content could already be used under the terms of its current license.
+    fn permissiveness(&self) -> u8 {
+        match self {
+            ContentLicense::AllRightsReserved => 0,
+            ContentLicense::Custom(_) => 1,
+            ContentLicense::CcBySa => 2,
+            ContentLicense::CcBy => 3,
+            ContentLicense::Cc0 => 4,
+        }
+    }
+}
+
+// Content that a blog moderator can pin a moderation note to.
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Encode, Decode, PartialEq)]
+pub enum ModeratedEntity<T: Trait> {
+  Post(T::PostId),
+  Comment(T::CommentId),
+}
+
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Encode, Decode, PartialEq)]
+pub struct ModerationNote<T: Trait> {
+  created: Change<T>,
+  ipfs_hash: Vec<u8>,
+  // A code of a moderation reason. Its meaning is defined by clients.
+  reason: u16,
+}
+
+// An entity whose stored counters can be audited by root.
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Encode, Decode, PartialEq)]
+pub enum AuditedEntity<T: Trait> {
+  Blog(T::BlogId),
+  Post(T::PostId),
+  Comment(T::CommentId),
+}
+
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
 #[derive(Clone, Copy, Encode, Decode, PartialEq, Eq)]
 pub enum ReactionKind {
     Upvote,
     Downvote,
+    // An index of a custom reaction defined by a blog owner for content in their blog.
+    Custom(u8),
 }
 
 impl Default for ReactionKind {
@@ -119,6 +503,29 @@ impl Default for ReactionKind {
     }
 }
 
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq)]
+pub enum BlogRole {
+    // Can create posts in a blog.
+    Author,
+    // Can create posts and update posts of other accounts in a blog.
+    Editor,
+    // Can hide comments on posts of a blog.
+    Moderator,
+}
+
+// Groups of extrinsics that can be paused separately from the rest of the module:
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq)]
+pub enum CallGroup {
+    // Creating and updating blogs, posts, comments and channel messages.
+    Posting,
+    // Creating, updating and deleting reactions.
+    Reacting,
+    // Following and unfollowing blogs.
+    Following,
+}
+
 #[cfg_attr(feature = "std", derive(Debug))]
 #[derive(Clone, Encode, Decode, PartialEq)]
 pub struct Reaction<T: Trait> {
@@ -128,81 +535,576 @@ pub struct Reaction<T: Trait> {
   kind: ReactionKind,
 }
 
+// Topics are not registered on chain, a client decides what a topic id stands for.
+pub type TopicId = u32;
+
+// Totals of a blog, so that clients don't have to walk every post to get them.
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Copy, Default, Encode, Decode, PartialEq, Eq)]
+pub struct BlogStats {
+  pub posts_count: u32,
+  pub comments_count: u32,
+  pub post_reactions_count: u32,
+  // Accounts that have commented in a blog at least once. Not decreased when a post leaves the blog.
+  pub commenters_count: u32,
+  // Kept apart from the list of followers, so that follower tiers and milestones do not have to read the list:
+  pub followers_count: u32,
+}
+
+// Amounts of synthetic content to pre-populate storage with, e.g. for benchmarks or a local devnet.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Clone, Copy, Default, Encode, Decode, PartialEq, Eq)]
+pub struct ContentSeed {
+  pub blogs: u32,
+  pub posts_per_blog: u32,
+  pub comments_per_post: u32,
+  pub followers_per_blog: u32,
+  pub reactions_per_post: u32,
+}
+
 const DEFAULT_SLUG_MIN_LEN: u32 = 5;
 const DEFAULT_SLUG_MAX_LEN: u32 = 50;
 
+// About a week with 6 sec blocks:
+const DEFAULT_SLUG_REDIRECT_PERIOD: u64 = 100_800;
+const DEFAULT_SLUG_CHANGE_COOLDOWN: u64 = 14_400;
+const DEFAULT_WRITER_INVITE_PERIOD: u64 = 100_800;
+const DEFAULT_MAX_BLOG_WRITERS: u32 = 20;
+
+const DEFAULT_HANDLE_MIN_LEN: u32 = 3;
+const DEFAULT_HANDLE_MAX_LEN: u32 = 30;
+// Handles of this length or shorter are premium and cost a fee:
+const DEFAULT_PREMIUM_HANDLE_MAX_LEN: u32 = 4;
+const DEFAULT_PREMIUM_HANDLE_FEE: u64 = 1_000;
+const DEFAULT_MAX_HANDLE_HISTORY_LEN: u32 = 10;
+
 const DEFAULT_BLOG_MAX_LEN: u32 = 1_000;
 const DEFAULT_POST_MAX_LEN: u32 = 10_000;
 const DEFAULT_COMMENT_MAX_LEN: u32 = 1_000;
 
+const DEFAULT_MAX_VIEWS_BATCH_LEN: u32 = 500;
+
+const DEFAULT_MIN_BLOCKS_BETWEEN_POSTS: u64 = 1;
+
+const DEFAULT_MAX_RECENT_POSTS_PER_BLOG: u32 = 20;
+// Posts returned by one call of posts_by_blog() or get_feed():
+const MAX_POSTS_PAGE_LEN: u64 = 100;
+
+const DEFAULT_MAX_UNFOLLOWS_PER_BLOCK: u32 = 100;
+const DEFAULT_MAX_ERASURE_STEPS_PER_BLOCK: u32 = 500;
+
+// Not configurable, so that a comment never moves to another page:
+const COMMENTS_PAGE_SIZE: u32 = 20;
+
+const DEFAULT_SPONSORED_POST_FEE: u64 = 100;
+const DEFAULT_APPEAL_BOND: u64 = 100;
+
+const DEFAULT_SUBMISSION_DEPOSIT: u64 = 10;
+const DEFAULT_SUBMISSION_PERIOD: u64 = 100_800;
+const DEFAULT_MAX_PENDING_SUBMISSIONS_PER_BLOG: u32 = 100;
+
+const DEFAULT_POSTING_FEE_HOLD_PERIOD: u64 = 100_800;
+
+// Should roughly cover a transaction fee of a follow, a comment or a reaction:
+const DEFAULT_SPONSORED_ACTION_REFUND: u64 = 1;
+const DEFAULT_MAX_SPONSORED_ACTIONS_PER_ACCOUNT: u32 = 100;
+
+// Limits bot waves on a single post or comment:
+const DEFAULT_MAX_REACTIONS_PER_ENTITY: u32 = 1_000_000;
+
+const DEFAULT_CHANNEL_MESSAGE_MAX_LEN: u32 = 100;
+const DEFAULT_MAX_CHANNEL_MESSAGES: u32 = 100;
+
+const DEFAULT_LIST_NAME_MAX_LEN: u32 = 100;
+const DEFAULT_MAX_LISTS_PER_ACCOUNT: u32 = 20;
+const DEFAULT_MAX_BLOGS_PER_LIST: u32 = 100;
+
+const DEFAULT_MAX_BOOKMARKS_PER_ACCOUNT: u32 = 1_000;
+
+const DEFAULT_IPFS_HASH_MAX_LEN: u32 = 100;
+const DEFAULT_MAX_POSTS_PER_SERIES: u32 = 100;
+
+const DEFAULT_MAX_CUSTOM_REACTIONS_PER_BLOG: u32 = 10;
+const DEFAULT_CUSTOM_REACTION_MAX_LEN: u32 = 20;
+
+const DEFAULT_MAX_TOPICS_PER_BLOG: u32 = 5;
+const DEFAULT_MAX_TOPICS_FOLLOWED_BY_ACCOUNT: u32 = 100;
+
+const DEFAULT_MAX_MEMBERSHIP_TIERS_PER_BLOG: u32 = 5;
+const DEFAULT_MEMBERSHIP_TIER_NAME_MAX_LEN: u32 = 50;
+
+const DEFAULT_MAX_MENTIONS_PER_CONTENT: u32 = 10;
+const DEFAULT_MAX_MENTIONS_PER_ACCOUNT: u32 = 1_000;
+
+const DEFAULT_MAX_UNREAD_REPLIES_PER_ACCOUNT: u32 = 100;
+
+const DEFAULT_MAX_MUTED_WORDS_PER_ACCOUNT: u32 = 20;
+const DEFAULT_MUTED_WORD_MAX_LEN: u32 = 50;
+
+const DEFAULT_MAX_LINKS_PER_POST: u32 = 20;
+const DEFAULT_MAX_COMMENTS_PER_MOVED_POST: u32 = 500;
+
+const DEFAULT_MAX_PERSONAS_PER_ACCOUNT: u32 = 10;
+const DEFAULT_PERSONA_NAME_MAX_LEN: u32 = 50;
+
+const DEFAULT_MAX_REPLY_DEPTH_TO_COUNT: u32 = 10;
+
+const DEFAULT_MAX_ORG_MEMBERS: u32 = 20;
+const DEFAULT_MAX_PENDING_PROPOSALS_PER_ORG: u32 = 20;
+
+// Numbers of blog followers that are celebrated with an event:
+const FOLLOWER_MILESTONES: [u32; 3] = [100, 1_000, 10_000];
+
 decl_storage! {
   trait Store for Module<T: Trait> as Blogs {
 
+    // Emergency brakes that can be pulled by root only:
+    Paused get(paused): bool;
+    PausedCallGroups get(paused_call_groups): Vec<CallGroup>;
+
     SlugMinLen get(slug_min_len): u32 = DEFAULT_SLUG_MIN_LEN;
     SlugMaxLen get(slug_max_len): u32 = DEFAULT_SLUG_MAX_LEN;
 
+    // For how many blocks an old blog slug redirects to its blog and cannot be taken by another blog:
+    SlugRedirectPeriod get(slug_redirect_period): T::BlockNumber = T::BlockNumber::sa(DEFAULT_SLUG_REDIRECT_PERIOD);
+
+    // How many blocks a blog owner should wait before they can change a slug of their blog again:
+    SlugChangeCooldown get(slug_change_cooldown): T::BlockNumber = T::BlockNumber::sa(DEFAULT_SLUG_CHANGE_COOLDOWN);
+
+    // For how many blocks an invited writer can accept an invite to a blog:
+    WriterInvitePeriod get(writer_invite_period): T::BlockNumber = T::BlockNumber::sa(DEFAULT_WRITER_INVITE_PERIOD);
+    MaxBlogWriters get(max_blog_writers): u32 = DEFAULT_MAX_BLOG_WRITERS;
+
+    HandleMinLen get(handle_min_len): u32 = DEFAULT_HANDLE_MIN_LEN;
+    HandleMaxLen get(handle_max_len): u32 = DEFAULT_HANDLE_MAX_LEN;
+    PremiumHandleMaxLen get(premium_handle_max_len): u32 = DEFAULT_PREMIUM_HANDLE_MAX_LEN;
+    // A fee that is burned when a blog takes a premium handle:
+    PremiumHandleFee get(premium_handle_fee): BalanceOf<T> = BalanceOf::<T>::sa(DEFAULT_PREMIUM_HANDLE_FEE);
+    MaxHandleHistoryLen get(max_handle_history_len): u32 = DEFAULT_MAX_HANDLE_HISTORY_LEN;
+
     BlogMaxLen get(blog_max_len): u32 = DEFAULT_BLOG_MAX_LEN;
     PostMaxLen get(post_max_len): u32 = DEFAULT_POST_MAX_LEN;
     CommentMaxLen get(comment_max_len): u32 = DEFAULT_COMMENT_MAX_LEN;
 
+    // Comment edit constraints. There is no limit if a value is not set:
+    MaxCommentEdits get(max_comment_edits): Option<u16>;
+    CommentEditWindow get(comment_edit_window): Option<T::BlockNumber>;
+
+    // An off-chain indexer account that is allowed to record post views:
+    ViewsOracle get(views_oracle): Option<T::AccountId>;
+
+    MaxViewsBatchLen get(max_views_batch_len): u32 = DEFAULT_MAX_VIEWS_BATCH_LEN;
+
+    // An account that is allowed to verify blogs, e.g. to protect from impersonation:
+    VerificationAuthority get(verification_authority): Option<T::AccountId>;
+
+    // Throttles posting into a blog, so that it cannot be flooded by bots:
+    MinBlocksBetweenPosts get(min_blocks_between_posts): T::BlockNumber = T::BlockNumber::sa(DEFAULT_MIN_BLOCKS_BETWEEN_POSTS);
+    // Tiers sorted by min followers. Limits of the highest reached tier override the default ones:
+    FollowerTiers get(follower_tiers): Vec<FollowerTier<T>>;
+
+    LastPostBlockByBlogId get(last_post_block_by_blog_id): map T::BlogId => Option<T::BlockNumber>;
+    PostThrottleExemptBlogs get(post_throttle_exempt_blogs): map T::BlogId => bool;
+
+    MaxRecentPostsPerBlog get(max_recent_posts_per_blog): u32 = DEFAULT_MAX_RECENT_POSTS_PER_BLOG;
+
+    // How many blogs can be unfollowed by reset_following() in one block:
+    MaxUnfollowsPerBlock get(max_unfollows_per_block): u32 = DEFAULT_MAX_UNFOLLOWS_PER_BLOCK;
+
+    // How many posts, comments, reactions and other items of an erased account can be processed in one block:
+    MaxErasureStepsPerBlock get(max_erasure_steps_per_block): u32 = DEFAULT_MAX_ERASURE_STEPS_PER_BLOCK;
+
+    // An account that replaces an erased account as an author of its content.
+    // Accounts cannot be erased until root sets it:
+    TombstoneAccount get(tombstone_account): Option<T::AccountId>;
+    ErasedAccounts get(erased_accounts): map T::AccountId => bool;
+    AccountErasureQueue get(account_erasure_queue): Vec<T::AccountId>;
+
+    // A fee that is burned when a sponsored post is created:
+    SponsoredPostFee get(sponsored_post_fee): BalanceOf<T> = BalanceOf::<T>::sa(DEFAULT_SPONSORED_POST_FEE);
+
+    // A bond that is reserved from an author who appeals against hiding of their comment:
+    AppealBond get(appeal_bond): BalanceOf<T> = BalanceOf::<T>::sa(DEFAULT_APPEAL_BOND);
+
+    // A deposit that is reserved from a guest author. It is slashed if a blog owner rejects the submission:
+    SubmissionDeposit get(submission_deposit): BalanceOf<T> = BalanceOf::<T>::sa(DEFAULT_SUBMISSION_DEPOSIT);
+    // For how many blocks a blog owner can approve a submitted post:
+    SubmissionPeriod get(submission_period): T::BlockNumber = T::BlockNumber::sa(DEFAULT_SUBMISSION_PERIOD);
+    MaxPendingSubmissionsPerBlog get(max_pending_submissions_per_blog): u32 = DEFAULT_MAX_PENDING_SUBMISSIONS_PER_BLOG;
+
+    // How much is refunded from a blog fee pot for a follow, a comment or a reaction in the blog.
+    // A refund never exceeds the base transaction fee, so an action cannot earn more than it cost:
+    SponsoredActionRefund get(sponsored_action_refund): BalanceOf<T> = BalanceOf::<T>::sa(DEFAULT_SPONSORED_ACTION_REFUND);
+    // How many actions of one account can be sponsored by one blog:
+    MaxSponsoredActionsPerAccount get(max_sponsored_actions_per_account): u32 = DEFAULT_MAX_SPONSORED_ACTIONS_PER_ACCOUNT;
+    // Only the first follow of a blog by an account is refunded:
+    HasSponsoredFollow get(has_sponsored_follow): map (T::BlogId, T::AccountId) => bool;
+
+    MaxReactionsPerPost get(max_reactions_per_post): u32 = DEFAULT_MAX_REACTIONS_PER_ENTITY;
+    MaxReactionsPerComment get(max_reactions_per_comment): u32 = DEFAULT_MAX_REACTIONS_PER_ENTITY;
+
+    // If enabled, changes of post reactions are reported with one event per post at the end of a block
+    // instead of an event per reaction:
+    AggregateReactionEvents get(aggregate_reaction_events): bool;
+    PostIdsWithReactionDeltas get(post_ids_with_reaction_deltas): Vec<T::PostId>;
+    ReactionDeltasByPostId get(reaction_deltas_by_post_id): map T::PostId => (i32, i32);
+
+    ChannelMessageMaxLen get(channel_message_max_len): u32 = DEFAULT_CHANNEL_MESSAGE_MAX_LEN;
+    // Only this many of the most recent messages are kept in a blog channel:
+    MaxChannelMessages get(max_channel_messages): u32 = DEFAULT_MAX_CHANNEL_MESSAGES;
+
+    ListNameMaxLen get(list_name_max_len): u32 = DEFAULT_LIST_NAME_MAX_LEN;
+    MaxListsPerAccount get(max_lists_per_account): u32 = DEFAULT_MAX_LISTS_PER_ACCOUNT;
+    MaxBlogsPerList get(max_blogs_per_list): u32 = DEFAULT_MAX_BLOGS_PER_LIST;
+
+    MaxBookmarksPerAccount get(max_bookmarks_per_account): u32 = DEFAULT_MAX_BOOKMARKS_PER_ACCOUNT;
+
+    IpfsHashMaxLen get(ipfs_hash_max_len): u32 = DEFAULT_IPFS_HASH_MAX_LEN;
+    MaxPostsPerSeries get(max_posts_per_series): u32 = DEFAULT_MAX_POSTS_PER_SERIES;
+
+    MaxCustomReactionsPerBlog get(max_custom_reactions_per_blog): u32 = DEFAULT_MAX_CUSTOM_REACTIONS_PER_BLOG;
+    CustomReactionMaxLen get(custom_reaction_max_len): u32 = DEFAULT_CUSTOM_REACTION_MAX_LEN;
+
+    MaxTopicsPerBlog get(max_topics_per_blog): u32 = DEFAULT_MAX_TOPICS_PER_BLOG;
+    MaxTopicsFollowedByAccount get(max_topics_followed_by_account): u32 = DEFAULT_MAX_TOPICS_FOLLOWED_BY_ACCOUNT;
+
+    MaxMembershipTiersPerBlog get(max_membership_tiers_per_blog): u32 = DEFAULT_MAX_MEMBERSHIP_TIERS_PER_BLOG;
+    MembershipTierNameMaxLen get(membership_tier_name_max_len): u32 = DEFAULT_MEMBERSHIP_TIER_NAME_MAX_LEN;
+
+    MaxMentionsPerContent get(max_mentions_per_content): u32 = DEFAULT_MAX_MENTIONS_PER_CONTENT;
+    MaxMentionsPerAccount get(max_mentions_per_account): u32 = DEFAULT_MAX_MENTIONS_PER_ACCOUNT;
+
+    MaxUnreadRepliesPerAccount get(max_unread_replies_per_account): u32 = DEFAULT_MAX_UNREAD_REPLIES_PER_ACCOUNT;
+
+    MaxMutedWordsPerAccount get(max_muted_words_per_account): u32 = DEFAULT_MAX_MUTED_WORDS_PER_ACCOUNT;
+    MutedWordMaxLen get(muted_word_max_len): u32 = DEFAULT_MUTED_WORD_MAX_LEN;
+
+    MaxPersonasPerAccount get(max_personas_per_account): u32 = DEFAULT_MAX_PERSONAS_PER_ACCOUNT;
+    PersonaNameMaxLen get(persona_name_max_len): u32 = DEFAULT_PERSONA_NAME_MAX_LEN;
+
+    MaxLinksPerPost get(max_links_per_post): u32 = DEFAULT_MAX_LINKS_PER_POST;
+    // Commenters of a moved post are carried over to its new blog one by one, so a post with more comments stays where it is:
+    MaxCommentsPerMovedPost get(max_comments_per_moved_post): u32 = DEFAULT_MAX_COMMENTS_PER_MOVED_POST;
+
+    // How many ancestors of a new comment get their total replies count increased:
+    MaxReplyDepthToCount get(max_reply_depth_to_count): u32 = DEFAULT_MAX_REPLY_DEPTH_TO_COUNT;
+
+    MaxOrgMembers get(max_org_members): u32 = DEFAULT_MAX_ORG_MEMBERS;
+    OrgById get(org_by_id): map u64 => Option<Org<T>>;
+    OrgIdsByMember get(org_ids_by_member): map T::AccountId => Vec<u64>;
+    OrgIdByAccount get(org_id_by_account): map T::AccountId => Option<u64>;
+
+    MaxPendingProposalsPerOrg get(max_pending_proposals_per_org): u32 = DEFAULT_MAX_PENDING_PROPOSALS_PER_ORG;
+    OrgProposalById get(org_proposal_by_id): map u64 => Option<OrgProposal<T>>;
+    PendingProposalIdsByOrgId get(pending_proposal_ids_by_org_id): map u64 => Vec<u64>;
+
     BlogById get(blog_by_id): map T::BlogId => Option<Blog<T>>;
     PostById get(post_by_id): map T::PostId => Option<Post<T>>;
     CommentById get(comment_by_id): map T::CommentId => Option<Comment<T>>;
     ReactionById get(reaction_by_id): map T::ReactionId => Option<Reaction<T>>;
+    BlogListById get(blog_list_by_id): map T::BlogListId => Option<BlogList<T>>;
 
     BlogIdsByOwner get(blog_ids_by_owner): map T::AccountId => Vec<T::BlogId>;
+    // Content of an account, so that an erasure of the account does not have to scan all content.
+    // A post on behalf of a blog is indexed by its writer:
+    PostIdsByAccount get(post_ids_by_account): map T::AccountId => Vec<T::PostId>;
+    CommentIdsByAccount get(comment_ids_by_account): map T::AccountId => Vec<T::CommentId>;
+    ReactionIdsByAccount get(reaction_ids_by_account): map T::AccountId => Vec<T::ReactionId>;
     PostIdsByBlogId get(post_ids_by_blog_id): map T::BlogId => Vec<T::PostId>;
+    // The most recent posts of a blog, oldest first. Used to build a home feed:
+    RecentPostIdsByBlogId get(recent_post_ids_by_blog_id): map T::BlogId => Vec<T::PostId>;
+    PostIdsByBlogAndLanguage get(post_ids_by_blog_and_language): map (T::BlogId, [u8; 2]) => Vec<T::PostId>;
     CommentIdsByPostId get(comment_ids_by_post_id): map T::PostId => Vec<T::CommentId>;
+    CommentIdsByPostIdAndPage get(comment_ids_by_post_id_and_page): map (T::PostId, u32) => Vec<T::CommentId>;
 
+    // Deprecated: replaced by ReactionIdByPostIdAndIndex. Only read by the migration:
     ReactionIdsByPostId get(reaction_ids_by_post_id): map T::PostId => Vec<T::ReactionId>;
+
+    // Reactions of a post are stored one per entry, so that a new reaction does not rewrite all previous ones:
+    PostReactionsCount get(post_reactions_count): map T::PostId => u32;
+    ReactionIdByPostIdAndIndex get(reaction_id_by_post_id_and_index): map (T::PostId, u32) => Option<T::ReactionId>;
+    ReactionIndexByPostId get(reaction_index_by_post_id): map (T::PostId, T::ReactionId) => Option<u32>;
     ReactionIdsByCommentId get(reaction_ids_by_comment_id): map T::CommentId => Vec<T::ReactionId>;
-    PostReactionIdByAccount get(post_reaction_id_by_account): map (T::AccountId, T::PostId) => T::ReactionId;
-    CommentReactionIdByAccount get(comment_reaction_id_by_account): map (T::AccountId, T::CommentId) => T::ReactionId;
+    // Labels of custom reactions of a blog. A custom reaction kind is an index in this list:
+    CustomReactionsByBlogId get(custom_reactions_by_blog_id): map T::BlogId => Vec<Vec<u8>>;
+
+    TopicsByBlogId get(topics_by_blog_id): map T::BlogId => Vec<TopicId>;
+    BlogIdsByTopic get(blog_ids_by_topic): map TopicId => Vec<T::BlogId>;
+    AccountsFollowingTopic get(accounts_following_topic): map TopicId => Vec<T::AccountId>;
+    TopicsFollowedByAccount get(topics_followed_by_account): map T::AccountId => Vec<TopicId>;
+    CustomReactionsCountByPostId get(custom_reactions_count_by_post_id): map (T::PostId, u8) => u32;
+    CustomReactionsCountByCommentId get(custom_reactions_count_by_comment_id): map (T::CommentId, u8) => u32;
+
+    PostReactionIdByAccount get(post_reaction_id_by_account): map (T::AccountId, T::PostId) => Option<T::ReactionId>;
+    CommentReactionIdByAccount get(comment_reaction_id_by_account): map (T::AccountId, T::CommentId) => Option<T::ReactionId>;
 
     BlogIdBySlug get(blog_id_by_slug): map Vec<u8> => Option<T::BlogId>;
+    // Old blog slug => (blog id, block number when the redirect expires)
+    SlugRedirects get(slug_redirect): map Vec<u8> => Option<(T::BlogId, T::BlockNumber)>;
+    LastSlugChangeByBlogId get(last_slug_change_by_blog_id): map T::BlogId => Option<T::BlockNumber>;
     PostIdBySlug get(post_id_by_slug): map Vec<u8> => Option<T::PostId>;
 
+    // A @handle of a blog is independent from its slug:
+    HandleByBlogId get(handle_by_blog_id): map T::BlogId => Option<Vec<u8>>;
+    BlogIdByHandle get(blog_id_by_handle): map Vec<u8> => Option<T::BlogId>;
+    // Previous handles of a blog and block numbers when they were released, oldest first:
+    HandleHistoryByBlogId get(handle_history_by_blog_id): map T::BlogId => Vec<(Vec<u8>, T::BlockNumber)>;
+
+    // Real writers of posts published on behalf of a blog. Note that chain storage is public,
+    // so this only hides the writer from clients that show the post author:
+    WriterByPostId get(writer_by_post_id): map T::PostId => Option<T::AccountId>;
+
+    // Licenses of a post and block numbers when they were set, oldest first:
+    LicenseHistoryByPostId get(license_history_by_post_id): map T::PostId => Vec<(ContentLicense, T::BlockNumber)>;
+
+    // Increases on every change of posts within a blog, so clients can cheaply detect whether they need to resync:
+    BlogContentSeq get(blog_content_seq): map T::BlogId => u64;
+
     BlogsFollowedByAccount get(blogs_followed_by_account): map T::AccountId => Vec<T::BlogId>;
     BlogFollowers get(blog_followers): map T::BlogId => Vec<T::AccountId>;
     BlogFollowedByAccount get(blog_followed_by_account): map (T::AccountId, T::BlogId) => bool;
+    // The highest follower milestone reached by a blog, so that it is not celebrated twice:
+    FollowerMilestoneByBlogId get(follower_milestone_by_blog_id): map T::BlogId => u32;
+    // Accounts whose following reset did not fit into one block, in the order of requests:
+    FollowingResetQueue get(following_reset_queue): Vec<T::AccountId>;
 
-    NextBlogId get(next_blog_id): T::BlogId = T::BlogId::sa(1);
-    NextPostId get(next_post_id): T::PostId = T::PostId::sa(1);
-    NextCommentId get(next_comment_id): T::CommentId = T::CommentId::sa(1);
-    NextReactionId get(next_reaction_id): T::ReactionId = T::ReactionId::sa(1);
-  }
-}
+    ListsByAccount get(lists_by_account): map T::AccountId => Vec<T::BlogListId>;
+    BlogIdsByList get(blog_ids_by_list): map T::BlogListId => Vec<T::BlogId>;
 
-decl_event! {
-  pub enum Event<T> where
-    <T as system::Trait>::AccountId,
-    <T as Trait>::BlogId,
-    <T as Trait>::PostId,
+    BookmarkedPostIdsByAccount get(bookmarked_post_ids_by_account): map T::AccountId => Vec<T::PostId>;
+
+    // The most recent posts and comments that mention an account:
+    MentionsByAccount get(mentions_by_account): map T::AccountId => Vec<Mention<T>>;
+
+    // The most recent replies to an account that it has not read yet, oldest first:
+    UnreadRepliesByAccount get(unread_replies_by_account): map T::AccountId => Vec<Reply<T>>;
+    RepliesCountByAccount get(replies_count_by_account): map T::AccountId => u64;
+
+    // An account is not notified about mentions and replies whose JSON contains any of its muted words:
+    MutedWordsByAccount get(muted_words_by_account): map T::AccountId => Vec<Vec<u8>>;
+
+    // Named public identities of an account. An index of a name is a persona id:
+    PersonasByAccount get(personas_by_account): map T::AccountId => Vec<Vec<u8>>;
+    // A persona that new posts and comments of an account are published under:
+    ActivePersonaByAccount get(active_persona_by_account): map T::AccountId => Option<u16>;
+    PersonaByPostId get(persona_by_post_id): map T::PostId => Option<u16>;
+    PersonaByCommentId get(persona_by_comment_id): map T::CommentId => Option<u16>;
+
+    // Posts that a post refers to, and posts that refer to a post:
+    LinksByPostId get(links_by_post_id): map T::PostId => Vec<T::PostId>;
+    BacklinksByPostId get(backlinks_by_post_id): map T::PostId => Vec<T::PostId>;
+
+    ChannelMessagesByBlogId get(channel_messages_by_blog_id): map T::BlogId => Vec<ChannelMessage<T>>;
+    NextChannelMessageId get(next_channel_message_id): map T::BlogId => u64;
+
+    RoleByBlogAndAccount get(role_by_blog_and_account): map (T::BlogId, T::AccountId) => Option<BlogRole>;
+    BlogIdsByRoleHolder get(blog_ids_by_role_holder): map T::AccountId => Vec<T::BlogId>;
+
+    // (blog id, invited account) => block number when the invite expires
+    WriterInviteByBlogAndAccount get(writer_invite_by_blog_and_account): map (T::BlogId, T::AccountId) => Option<T::BlockNumber>;
+    InvitedBlogIdsByAccount get(invited_blog_ids_by_account): map T::AccountId => Vec<T::BlogId>;
+    BlogIdsByWriter get(blog_ids_by_writer): map T::AccountId => Vec<T::BlogId>;
+
+    // A blog owner or a blog moderator that hid a comment of another account:
+    CommentHiddenByModerator get(comment_hidden_by_moderator): map T::CommentId => Option<T::AccountId>;
+
+    // Blogs and posts that root has marked as NSFW. Their owners cannot clear the flag:
+    NsfwForcedByBlogId get(nsfw_forced_by_blog_id): map T::BlogId => bool;
+    NsfwForcedByPostId get(nsfw_forced_by_post_id): map T::PostId => bool;
+
+    // Can be removed only by a moderator who pinned it or by root, but not by a content author:
+    ModerationNoteByEntity get(moderation_note_by_entity): map ModeratedEntity<T> => Option<ModerationNote<T>>;
+    SeriesById get(series_by_id): map u64 => Option<PostSeries<T>>;
+    SeriesIdsByBlogId get(series_ids_by_blog_id): map T::BlogId => Vec<u64>;
+    SeriesIdsByAccount get(series_ids_by_account): map T::AccountId => Vec<u64>;
+    // Posts of a series in the order they should be read:
+    PostIdsBySeriesId get(post_ids_by_series_id): map u64 => Vec<T::PostId>;
+
+    MembershipTiersByBlogId get(membership_tiers_by_blog_id): map T::BlogId => Vec<MembershipTier<T>>;
+    MembershipById get(membership_by_id): map u64 => Option<BlogMembership<T>>;
+    MembershipIdsByAccount get(membership_ids_by_account): map T::AccountId => Vec<u64>;
+
+    // Funds reserved by a blog owner to refund fees of actions in their blog:
+    FeePotByBlogId get(fee_pot_by_blog_id): map T::BlogId => BalanceOf<T>;
+
+    BlogStatsByBlogId get(blog_stats_by_blog_id): map T::BlogId => BlogStats;
+    HasCommentedInBlog get(has_commented_in_blog): map (T::BlogId, T::AccountId) => bool;
+
+    // A fee that accounts other than a blog owner pay for every post in the blog. It is reserved from them
+    // and goes to the owner only if the owner does not accept the post within the hold period:
+    PostingFeeByBlogId get(posting_fee_by_blog_id): map T::BlogId => BalanceOf<T>;
+    // For how many blocks a blog owner can accept a post and refund its posting fee.
+    // After that the fee goes to the blog owner:
+    PostingFeeHoldPeriod get(posting_fee_hold_period): T::BlockNumber = T::BlockNumber::sa(DEFAULT_POSTING_FEE_HOLD_PERIOD);
+    PostingFeePaidByPostId get(posting_fee_paid_by_post_id): map T::PostId => Option<PostingFee<T>>;
+    SponsoredActionsCount get(sponsored_actions_count): map (T::BlogId, T::AccountId) => u32;
+
+    AppealByCommentId get(appeal_by_comment_id): map T::CommentId => Option<Appeal<T>>;
+
+    SubmissionById get(submission_by_id): map u64 => Option<PostSubmission<T>>;
+    PendingSubmissionIdsByBlogId get(pending_submission_ids_by_blog_id): map T::BlogId => Vec<u64>;
+    PendingSubmissionIdsByAccount get(pending_submission_ids_by_account): map T::AccountId => Vec<u64>;
+
+    NextBlogId get(next_blog_id): T::BlogId = T::BlogId::sa(1);
+    NextPostId get(next_post_id): T::PostId = T::PostId::sa(1);
+    NextCommentId get(next_comment_id): T::CommentId = T::CommentId::sa(1);
+    NextReactionId get(next_reaction_id): T::ReactionId = T::ReactionId::sa(1);
+    NextBlogListId get(next_blog_list_id): T::BlogListId = T::BlogListId::sa(1);
+    NextMembershipId get(next_membership_id): u64 = 1;
+    NextSeriesId get(next_series_id): u64 = 1;
+    NextOrgId get(next_org_id): u64 = 1;
+    NextOrgProposalId get(next_org_proposal_id): u64 = 1;
+    NextSubmissionId get(next_submission_id): u64 = 1;
+  }
+  add_extra_genesis {
+    // Authors of the seeded content. No content is seeded if empty.
+    config(seed_accounts): Vec<T::AccountId>;
+    config(content_seed): ContentSeed;
+    build(|storage: &mut runtime_primitives::StorageOverlay, _: &mut runtime_primitives::ChildrenStorageOverlay, config: &GenesisConfig<T>| {
+      runtime_io::with_storage(storage, || {
+        <Module<T>>::seed_content(&config.seed_accounts, config.content_seed);
+      });
+    });
+  }
+}
+
+decl_event! {
+  pub enum Event<T> where
+    <T as system::Trait>::AccountId,
+    <T as Trait>::BlogId,
+    <T as Trait>::PostId,
     <T as Trait>::CommentId,
-    <T as Trait>::ReactionId
+    <T as Trait>::ReactionId,
+    <T as Trait>::BlogListId,
+    Balance = BalanceOf<T>
   {
     BlogCreated(AccountId, BlogId),
     BlogUpdated(AccountId, BlogId),
+    BlogHandleChanged(AccountId, BlogId),
+    // Params: blog id, old slug, new slug.
+    BlogSlugChanged(BlogId, Vec<u8>, Vec<u8>),
     BlogDeleted(AccountId, BlogId),
 
     BlogFollowed(AccountId, BlogId),
     BlogUnfollowed(AccountId, BlogId),
+    BlogFollowersMilestoneReached(BlogId, u32),
+
+    BlogTopicsUpdated(AccountId, BlogId),
+    TopicFollowed(AccountId, TopicId),
+    TopicUnfollowed(AccountId, TopicId),
+
+    FeePotDeposited(AccountId, BlogId, Balance),
+    FeePotWithdrawn(AccountId, BlogId, Balance),
+    PostingFeeSet(AccountId, BlogId, Balance),
+    PostingFeePaid(AccountId, PostId, Balance),
+    // Params: blog owner, post id, posting fee that the owner got.
+    PostingFeeCollected(AccountId, PostId, Balance),
+    // Params: blog owner, post id, refunded posting fee.
+    PostAccepted(AccountId, PostId, Balance),
+    ActionSponsored(AccountId, BlogId, Balance),
+    FollowingReset(AccountId),
+    AccountErasureRequested(AccountId),
+    AccountErased(AccountId),
+
+    BlogArchived(AccountId, BlogId),
+    BlogUnarchived(AccountId, BlogId),
+
+    BlogChannelUpdated(AccountId, BlogId),
+    ChannelMessagePosted(AccountId, BlogId, u64),
+    ChannelMessageDeleted(AccountId, BlogId, u64),
+
+    BlogVerified(AccountId, BlogId),
+    BlogUnverified(AccountId, BlogId),
+    BlogNsfwChanged(BlogId, bool),
+
+    BlogRoleGranted(AccountId, BlogId, AccountId, BlogRole),
+    BlogRoleRevoked(AccountId, BlogId, AccountId, BlogRole),
+
+    CustomReactionsUpdated(AccountId, BlogId),
+
+    MembershipTiersUpdated(AccountId, BlogId),
+    MembershipBought(AccountId, BlogId, u64),
+    MembershipTransferred(AccountId, AccountId, u64),
+
+    OrgCreated(AccountId, u64),
+    OrgMemberAdded(AccountId, u64, AccountId),
+    OrgMemberRemoved(AccountId, u64, AccountId),
+    OrgThresholdChanged(AccountId, u64, u16),
+    // Params: member, org id, proposal id.
+    OrgCallProposed(AccountId, u64, u64),
+    OrgCallApproved(AccountId, u64),
+    OrgProposalCancelled(AccountId, u64),
+    // Params: member whose approval completed the threshold, org id.
+    OrgCallDispatched(AccountId, u64),
+
+    WriterInvited(AccountId, BlogId, AccountId),
+    WriterInviteAccepted(AccountId, BlogId),
+    WriterInviteDeclined(AccountId, BlogId),
+
+    BlogListCreated(AccountId, BlogListId),
+    BlogListUpdated(AccountId, BlogListId),
+    BlogAddedToList(AccountId, BlogListId, BlogId),
+    BlogRemovedFromList(AccountId, BlogListId, BlogId),
 
     PostCreated(AccountId, PostId),
+    PostSubmitted(AccountId, BlogId, u64),
+    // Params: blog owner, submission id, id of a published post.
+    SubmissionApproved(AccountId, u64, PostId),
+    SubmissionRejected(AccountId, u64),
+    SubmissionWithdrawn(AccountId, u64),
+    SubmissionExpired(AccountId, u64),
+    SponsoredPostCreated(AccountId, PostId, Balance),
     PostUpdated(AccountId, PostId),
+    // Params: post id, old blog id, new blog id.
+    PostMoved(PostId, BlogId, BlogId),
     PostDeleted(AccountId, PostId),
+    PostViewsRecorded(AccountId, u32),
+    PostHidden(AccountId, PostId),
+    PostUnhidden(AccountId, PostId),
+    PostNsfwChanged(PostId, bool),
+    SeriesCreated(AccountId, u64),
+    SeriesUpdated(AccountId, u64),
+
+    RepliesRead(AccountId, u64),
+    MutedWordsUpdated(AccountId),
+
+    PersonaAdded(AccountId, u16),
+    PersonaRenamed(AccountId, u16),
+    ActivePersonaChanged(AccountId, Option<u16>),
+    PostCreatedAsPersona(AccountId, PostId, u16),
+    CommentCreatedAsPersona(AccountId, CommentId, u16),
+
+    PostBookmarked(AccountId, PostId),
+    PostUnbookmarked(AccountId, PostId),
 
     CommentCreated(AccountId, CommentId),
     CommentUpdated(AccountId, CommentId),
     CommentDeleted(AccountId, CommentId),
+    CommentHidden(AccountId, CommentId),
+    CommentUnhidden(AccountId, CommentId),
+
+    PostModerationNoteSet(AccountId, PostId),
+    PostModerationNoteRemoved(AccountId, PostId),
+    CommentModerationNoteSet(AccountId, CommentId),
+    CommentModerationNoteRemoved(AccountId, CommentId),
+    AppealOpened(AccountId, CommentId, Balance),
+    // Params: comment id, whether the bond has been refunded, whether the comment has been restored.
+    AppealResolved(CommentId, bool, bool),
 
     PostReactionCreated(AccountId, PostId, ReactionId),
     PostReactionUpdated(AccountId, PostId, ReactionId),
     PostReactionDeleted(AccountId, PostId, ReactionId),
+    // Params: post id, change of upvotes, change of downvotes during a block.
+    PostReactionsAggregated(PostId, i32, i32),
 
     CommentReactionCreated(AccountId, CommentId, ReactionId),
     CommentReactionUpdated(AccountId, CommentId, ReactionId),
     CommentReactionDeleted(AccountId, CommentId, ReactionId),
+
+    // Params: entity id, whether the counters have been fixed.
+    BlogCountersMismatch(BlogId, bool),
+    PostCountersMismatch(PostId, bool),
+    CommentCountersMismatch(CommentId, bool),
   }
 }
 
@@ -212,22 +1114,28 @@ decl_module! {
     fn deposit_event<T>() = default;
 
     fn on_initialize(_now: T::BlockNumber) {
-      // Stub
+      Self::continue_following_resets();
+      Self::continue_account_erasures();
     }
 
     fn on_finalize(_now: T::BlockNumber) {
-      // Stub
+      for post_id in <PostIdsWithReactionDeltas<T>>::take() {
+        let (upvotes_delta, downvotes_delta) = <ReactionDeltasByPostId<T>>::take(post_id);
+        Self::deposit_event(RawEvent::PostReactionsAggregated(post_id, upvotes_delta, downvotes_delta));
+      }
     }
 
     // TODO use BlogUpdate to pass data
-    fn create_blog(origin, slug: Vec<u8>, json: Vec<u8>) {
+    fn create_blog(origin, slug: Vec<u8>, json: Vec<u8>, language: Option<[u8; 2]>) {
       let owner = ensure_signed(origin)?;
+      Self::ensure_call_group_is_not_paused(CallGroup::Posting)?;
+      Self::ensure_account_is_not_erased(&owner)?;
 
-      ensure!(slug.len() >= Self::slug_min_len() as usize, "Blog slug is too short");
-      ensure!(slug.len() <= Self::slug_max_len() as usize, "Blog slug is too long");
-      ensure!(!<BlogIdBySlug<T>>::exists(slug.clone()), "Blog slug is not unique");
+      Self::ensure_blog_slug_is_valid(&slug)?;
+      Self::ensure_blog_slug_is_free(&slug, None)?;
 
       ensure!(json.len() <= Self::blog_max_len() as usize, "Blog JSON is too long");
+      Self::ensure_language_is_valid(&language)?;
 
       let blog_id = Self::next_blog_id();
       let new_blog: Blog<T> = Blog {
@@ -237,11 +1145,18 @@ decl_module! {
         writers: vec![],
         slug: slug.clone(),
         json,
-        posts_count: 0
+        posts_count: 0,
+        verified: None,
+        channel: None,
+        status: BlogStatus::Active,
+        language,
+        nsfw: false,
       };
 
       <BlogById<T>>::insert(blog_id, new_blog);
       <BlogIdsByOwner<T>>::mutate(owner.clone(), |ids| ids.push(blog_id));
+      // A redirect could be here only if it has been expired:
+      <SlugRedirects<T>>::remove(slug.clone());
       <BlogIdBySlug<T>>::insert(slug, blog_id);
       <NextBlogId<T>>::mutate(|n| { *n += T::BlogId::sa(1); });
       Self::deposit_event(RawEvent::BlogCreated(owner.clone(), blog_id));
@@ -250,429 +1165,3355 @@ decl_module! {
       Self::add_blog_follower(owner.clone(), blog_id);
     }
 
+    // Set, change or remove (if None) a @handle of a blog.
+    fn set_blog_handle(origin, blog_id: T::BlogId, handle: Option<Vec<u8>>) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let blog = Self::blog_by_id(blog_id).ok_or("Blog was not found by id")?;
+      Self::ensure_blog_is_active(&blog)?;
+      ensure!(owner == blog.created.account, "Only a blog owner can set a handle of their blog");
+
+      let old_handle = Self::handle_by_blog_id(blog_id);
+      ensure!(handle != old_handle, "Blog already has this handle");
+
+      if let Some(ref new_handle) = handle {
+        Self::ensure_handle_is_valid(new_handle)?;
+        ensure!(!<BlogIdByHandle<T>>::exists(new_handle), "Handle is already taken by another blog");
+
+        if new_handle.len() <= Self::premium_handle_max_len() as usize {
+          let fee = Self::premium_handle_fee();
+          ensure!(T::Currency::can_slash(&owner, fee), "Not enough balance to pay for a premium handle");
+          let _ = T::Currency::slash(&owner, fee);
+        }
+      }
+
+      if let Some(old_handle) = old_handle {
+        <BlogIdByHandle<T>>::remove(&old_handle);
+        let max_history_len = Self::max_handle_history_len() as usize;
+        let now = <system::Module<T>>::block_number();
+        <HandleHistoryByBlogId<T>>::mutate(blog_id, |history| {
+          if history.len() >= max_history_len && !history.is_empty() {
+            history.remove(0);
+          }
+          history.push((old_handle, now));
+        });
+      }
+
+      match handle {
+        Some(new_handle) => {
+          <BlogIdByHandle<T>>::insert(new_handle.clone(), blog_id);
+          <HandleByBlogId<T>>::insert(blog_id, new_handle);
+        },
+        None => <HandleByBlogId<T>>::remove(blog_id),
+      }
+      Self::deposit_event(RawEvent::BlogHandleChanged(owner, blog_id));
+    }
+
     fn follow_blog(origin, blog_id: T::BlogId) {
       let owner = ensure_signed(origin)?;
+      Self::ensure_call_group_is_not_paused(CallGroup::Following)?;
+      Self::ensure_account_is_not_erased(&owner)?;
 
-      Self::ensure_blog_exists(blog_id)?;
-      ensure!(<BlogFollowedByAccount<T>>::exists((owner.clone(), blog_id)), "Account is already following this blog");
+      let blog = Self::blog_by_id(blog_id).ok_or("Blog was not found by id")?;
+      Self::ensure_blog_is_active(&blog)?;
+      ensure!(!<BlogFollowedByAccount<T>>::exists((owner.clone(), blog_id)), "Account is already following this blog");
 
       Self::add_blog_follower(owner.clone(), blog_id);
+      Self::refund_sponsored_follow(&owner, &blog);
     }
 
     fn unfollow_blog(origin, blog_id: T::BlogId) {
       let owner = ensure_signed(origin)?;
+      Self::ensure_call_group_is_not_paused(CallGroup::Following)?;
+      Self::ensure_account_is_not_erased(&owner)?;
 
       Self::ensure_blog_exists(blog_id)?;
+      ensure!(<BlogFollowedByAccount<T>>::exists((owner.clone(), blog_id)), "Account is not following this blog");
 
-      <BlogsFollowedByAccount<T>>::mutate(owner.clone(), |blog_ids| {
-        if let Some(index) = blog_ids.iter().position(|x| *x == blog_id) {
-          blog_ids.swap_remove(index);
-        }
-      });
-      <BlogFollowers<T>>::mutate(blog_id, |account_ids| {
-        if let Some(index) = account_ids.iter().position(|x| *x == owner.clone()) {
-          account_ids.swap_remove(index);
-        }
-      });
-      <BlogFollowedByAccount<T>>::remove((owner.clone(), blog_id));
-      Self::deposit_event(RawEvent::BlogUnfollowed(owner.clone(), blog_id));
+      Self::remove_blog_follower(owner.clone(), blog_id);
     }
 
-    // TODO use PostUpdate to pass data?
-    fn create_post(origin, blog_id: T::BlogId, slug: Vec<u8>, json: Vec<u8>) {
+    // Same as follow_blog(), but succeeds with no changes if an account already follows a blog.
+    // This lets clients safely retry a follow.
+    fn ensure_follow_blog(origin, blog_id: T::BlogId) {
       let owner = ensure_signed(origin)?;
+      Self::ensure_call_group_is_not_paused(CallGroup::Following)?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let blog = Self::blog_by_id(blog_id).ok_or("Blog was not found by id")?;
+      if !<BlogFollowedByAccount<T>>::exists((owner.clone(), blog_id)) {
+        Self::ensure_blog_is_active(&blog)?;
+        Self::add_blog_follower(owner.clone(), blog_id);
+        Self::refund_sponsored_follow(&owner, &blog);
+      }
+    }
 
-      let mut blog = Self::blog_by_id(blog_id).ok_or("Blog was not found by id")?;
-
-      ensure!(slug.len() >= Self::slug_min_len() as usize, "Post slug is too short");
-      ensure!(slug.len() <= Self::slug_max_len() as usize, "Post slug is too long");
-      ensure!(!<PostIdBySlug<T>>::exists(slug.clone()), "Post slug is not unique");
-
-      ensure!(json.len() <= Self::post_max_len() as usize, "Post JSON is too long");
-
-      let post_id = Self::next_post_id();
-      let new_post: Post<T> = Post {
-        id: post_id,
-        blog_id,
-        created: Self::new_change(owner.clone()),
-        updated: None,
-        slug: slug.clone(),
-        json,
-        comments_count: 0,
-        upvotes_count: 0,
-        downvotes_count: 0,
-      };
-
-      <PostById<T>>::insert(post_id, new_post);
-      <PostIdsByBlogId<T>>::mutate(blog_id, |ids| ids.push(post_id));
-      <PostIdBySlug<T>>::insert(slug, post_id);
-      <NextPostId<T>>::mutate(|n| { *n += T::PostId::sa(1); });
-      Self::deposit_event(RawEvent::PostCreated(owner.clone(), post_id));
+    // Same as unfollow_blog(), but succeeds with no changes if an account does not follow a blog.
+    fn ensure_unfollow_blog(origin, blog_id: T::BlogId) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_call_group_is_not_paused(CallGroup::Following)?;
+      Self::ensure_account_is_not_erased(&owner)?;
 
-      blog.posts_count += 1;
-      <BlogById<T>>::insert(blog_id, blog); // TODO maybe use mutate instead of insert?
+      Self::ensure_blog_exists(blog_id)?;
+      if <BlogFollowedByAccount<T>>::exists((owner.clone(), blog_id)) {
+        Self::remove_blog_follower(owner.clone(), blog_id);
+      }
     }
 
-    // TODO use CommentUpdate to pass data?
-    fn create_comment(origin, post_id: T::PostId, parent_id: Option<T::CommentId>, json: Vec<u8>) {
+    // Hand over all content of the caller to the tombstone account and stop the caller
+    // from any further actions in this module. Content, open appeals, pending submissions,
+    // writer invites and org memberships of the caller are processed over the next blocks.
+    // Fee pots of the caller's blogs are returned to the caller, and their paid features are turned off,
+    // so that the tombstone account never receives or spends funds of the caller.
+    fn request_account_erasure(origin) {
       let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
 
-      let mut post = Self::post_by_id(post_id).ok_or("Post was not found by id")?;
+      let tombstone = Self::tombstone_account().ok_or("Accounts cannot be erased until a tombstone account is set")?;
+      ensure!(owner != tombstone, "Tombstone account cannot be erased");
 
-      if let Some(id) = parent_id {
-        ensure!(<CommentById<T>>::exists(id), "Unknown parent comment id");
+      <ErasedAccounts<T>>::insert(owner.clone(), true);
+
+      for blog_id in <BlogIdsByOwner<T>>::take(owner.clone()) {
+        Self::release_blog_funds(&owner, blog_id);
+        // A handle of a blog is a name of its owner, so it is not handed over to the tombstone account:
+        if let Some(handle) = <HandleByBlogId<T>>::take(blog_id) {
+          <BlogIdByHandle<T>>::remove(handle);
+        }
+        <BlogById<T>>::mutate(blog_id, |blog_opt| {
+          if let Some(blog) = blog_opt.as_mut() {
+            blog.created.account = tombstone.clone();
+          }
+        });
+        <BlogIdsByOwner<T>>::mutate(tombstone.clone(), |ids| ids.push(blog_id));
       }
 
-      ensure!(json.len() <= Self::comment_max_len() as usize, "Comment JSON is too long");
+      for blog_id in <BlogIdsByWriter<T>>::take(owner.clone()) {
+        <BlogById<T>>::mutate(blog_id, |blog_opt| {
+          if let Some(blog) = blog_opt.as_mut() {
+            blog.writers.retain(|w| *w != owner);
+          }
+        });
+      }
+      for blog_id in <BlogIdsByRoleHolder<T>>::take(owner.clone()) {
+        <RoleByBlogAndAccount<T>>::remove((blog_id, owner.clone()));
+      }
+      for list_id in <ListsByAccount<T>>::take(owner.clone()) {
+        <BlogListById<T>>::mutate(list_id, |list_opt| {
+          if let Some(list) = list_opt.as_mut() {
+            list.created.account = tombstone.clone();
+          }
+        });
+        <ListsByAccount<T>>::mutate(tombstone.clone(), |ids| ids.push(list_id));
+      }
 
-      let comment_id = Self::next_comment_id();
-      let new_comment: Comment<T> = Comment {
-        id: comment_id,
-        parent_id,
-        post_id,
-        created: Self::new_change(owner.clone()),
-        updated: None,
-        json,
-        upvotes_count: 0,
-        downvotes_count: 0,
-      };
+      <BookmarkedPostIdsByAccount<T>>::remove(owner.clone());
+      <MentionsByAccount<T>>::remove(owner.clone());
+      <UnreadRepliesByAccount<T>>::remove(owner.clone());
+      for topic in <TopicsFollowedByAccount<T>>::take(owner.clone()) {
+        Self::remove_topic_follower(&owner, topic);
+      }
 
-      <CommentById<T>>::insert(comment_id, new_comment);
-      <CommentIdsByPostId<T>>::mutate(post_id, |ids| ids.push(comment_id));
-      <NextCommentId<T>>::mutate(|n| { *n += T::CommentId::sa(1); });
-      Self::deposit_event(RawEvent::CommentCreated(owner.clone(), comment_id));
+      if !Self::blogs_followed_by_account(owner.clone()).is_empty() &&
+        !Self::following_reset_queue().contains(&owner)
+      {
+        <FollowingResetQueue<T>>::mutate(|queue| queue.push(owner.clone()));
+      }
 
-      post.comments_count += 1;
-      <PostById<T>>::insert(post_id, post); // TODO maybe use mutate instead of insert?
+      <AccountErasureQueue<T>>::mutate(|queue| queue.push(owner.clone()));
+      Self::deposit_event(RawEvent::AccountErasureRequested(owner));
     }
 
-    fn create_post_reaction(origin, post_id: T::PostId, kind: ReactionKind) {
+    // Unfollow all blogs. If an account follows too many blogs,
+    // the rest of them are unfollowed in the next blocks.
+    fn reset_following(origin) {
       let owner = ensure_signed(origin)?;
+      Self::ensure_call_group_is_not_paused(CallGroup::Following)?;
+      Self::ensure_account_is_not_erased(&owner)?;
 
-      ensure!(
-        !<PostReactionIdByAccount<T>>::exists((owner.clone(), post_id)),
-        "Account has already reacted to this post. To change a kind of reaction call update_post_reaction()"
-      );
-
-      let mut post = Self::post_by_id(post_id).ok_or("Post was not found by id")?;
-      let reaction_id = Self::new_reaction(owner.clone(), kind.clone());
-
-      <ReactionIdsByPostId<T>>::mutate(post_id, |ids| ids.push(reaction_id));
-      <PostReactionIdByAccount<T>>::insert((owner.clone(), post_id), reaction_id);
+      ensure!(!Self::blogs_followed_by_account(owner.clone()).is_empty(), "Account does not follow any blog");
+      ensure!(!Self::following_reset_queue().contains(&owner), "Following reset is already in progress");
 
-      match kind {
-        ReactionKind::Upvote => post.upvotes_count += 1,
-        ReactionKind::Downvote => post.downvotes_count += 1,
+      Self::unfollow_blogs_batch(owner.clone(), Self::max_unfollows_per_block());
+      if Self::blogs_followed_by_account(owner.clone()).is_empty() {
+        Self::deposit_event(RawEvent::FollowingReset(owner));
+      } else {
+        <FollowingResetQueue<T>>::mutate(|queue| queue.push(owner));
       }
-      // TODO maybe use mutate instead of insert?
-      <PostById<T>>::insert(post_id, post);
-
-      Self::deposit_event(RawEvent::PostReactionCreated(owner.clone(), post_id, reaction_id));
     }
 
-    fn create_comment_reaction(origin, comment_id: T::CommentId, kind: ReactionKind) {
+    fn archive_blog(origin, blog_id: T::BlogId) {
       let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
 
-      ensure!(
-        !<CommentReactionIdByAccount<T>>::exists((owner.clone(), comment_id)),
-        "Account has already reacted to this comment. To change a kind of reaction call update_comment_reaction()"
-      );
+      let mut blog = Self::blog_by_id(blog_id).ok_or("Blog was not found by id")?;
+      ensure!(owner == blog.created.account, "Only a blog owner can archive their blog");
+      ensure!(blog.status != BlogStatus::Archived, "Blog is already archived");
 
-      let mut comment = Self::comment_by_id(comment_id).ok_or("Comment was not found by id")?;
-      let reaction_id = Self::new_reaction(owner.clone(), kind.clone());
+      blog.status = BlogStatus::Archived;
+      blog.updated = Some(Self::new_change(owner.clone()));
+      <BlogById<T>>::insert(blog_id, blog);
+      Self::deposit_event(RawEvent::BlogArchived(owner.clone(), blog_id));
+    }
 
-      <ReactionIdsByCommentId<T>>::mutate(comment_id, |ids| ids.push(reaction_id));
-      <CommentReactionIdByAccount<T>>::insert((owner.clone(), comment_id), reaction_id);
+    fn unarchive_blog(origin, blog_id: T::BlogId) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
 
-      match kind {
-        ReactionKind::Upvote => comment.upvotes_count += 1,
-        ReactionKind::Downvote => comment.downvotes_count += 1,
-      }
-      // TODO maybe use mutate instead of insert?
-      <CommentById<T>>::insert(comment_id, comment);
+      let mut blog = Self::blog_by_id(blog_id).ok_or("Blog was not found by id")?;
+      ensure!(owner == blog.created.account, "Only a blog owner can unarchive their blog");
+      ensure!(blog.status == BlogStatus::Archived, "Blog is not archived");
 
-      Self::deposit_event(RawEvent::CommentReactionCreated(owner.clone(), comment_id, reaction_id));
+      blog.status = BlogStatus::Active;
+      blog.updated = Some(Self::new_change(owner.clone()));
+      <BlogById<T>>::insert(blog_id, blog);
+      Self::deposit_event(RawEvent::BlogUnarchived(owner.clone(), blog_id));
     }
 
-    fn update_blog(origin, blog_id: T::BlogId, update: BlogUpdate<T>) {
+    fn set_blog_channel(origin, blog_id: T::BlogId, channel: Option<ChannelPosters>) {
       let owner = ensure_signed(origin)?;
-      
-      let has_updates = 
-        update.writers.is_some() ||
-        update.slug.is_some() ||
-        update.json.is_some();
-
-      ensure!(has_updates, "Nothing to update in a blog");
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
 
       let mut blog = Self::blog_by_id(blog_id).ok_or("Blog was not found by id")?;
+      ensure!(owner == blog.created.account, "Only a blog owner can change a channel of their blog");
+      ensure!(channel != blog.channel, "Blog channel settings are the same as requested");
 
-      // TODO ensure: blog writers also should be able to edit this blog:
-      ensure!(owner == blog.created.account, "Only a blog owner can update their blog");
+      blog.channel = channel;
+      blog.updated = Some(Self::new_change(owner.clone()));
+      <BlogById<T>>::insert(blog_id, blog);
+      Self::deposit_event(RawEvent::BlogChannelUpdated(owner.clone(), blog_id));
+    }
 
-      let mut fields_updated = 0;
+    fn post_channel_message(origin, blog_id: T::BlogId, ipfs_hash: Vec<u8>) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_call_group_is_not_paused(CallGroup::Posting)?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let blog = Self::blog_by_id(blog_id).ok_or("Blog was not found by id")?;
+      Self::ensure_blog_is_active(&blog)?;
+      let channel = blog.channel.ok_or("Blog has no channel")?;
+
+      let can_post = match channel {
+        ChannelPosters::Followers => Self::blog_followed_by_account((owner.clone(), blog_id)),
+        ChannelPosters::Writers =>
+          Self::can_create_post(&owner, &blog) ||
+          Self::role_by_blog_and_account((blog_id, owner.clone())).is_some(),
+      };
+      ensure!(can_post, "Account has no permission to post to this channel");
 
-      if let Some(writers) = update.writers {
-        if writers != blog.writers {
-          // TODO validate writers.
-          // TODO update BlogIdsByWriter: insert new, delete removed, update only changed writers.
-          blog.writers = writers;
-          fields_updated += 1;
-        }
-      }
+      ensure!(!ipfs_hash.is_empty(), "Channel message IPFS hash cannot be empty");
+      ensure!(ipfs_hash.len() <= Self::channel_message_max_len() as usize, "Channel message IPFS hash is too long");
 
-      if let Some(slug) = update.slug {
-        if slug != blog.slug {
-          // TODO validate slug.
-          ensure!(!<BlogIdBySlug<T>>::exists(slug.clone()), "Blog slug is not unique");
-          <BlogIdBySlug<T>>::remove(blog.slug);
-          <BlogIdBySlug<T>>::insert(slug.clone(), blog_id);
-          blog.slug = slug;
-          fields_updated += 1;
-        }
-      }
+      let message_id = Self::next_channel_message_id(blog_id);
+      let message: ChannelMessage<T> = ChannelMessage {
+        id: message_id,
+        created: Self::new_change(owner.clone()),
+        ipfs_hash,
+      };
 
-      if let Some(json) = update.json {
-        if json != blog.json {
-          // TODO validate json.
-          blog.json = json;
-          fields_updated += 1;
+      let max_messages = Self::max_channel_messages() as usize;
+      <ChannelMessagesByBlogId<T>>::mutate(blog_id, |messages| {
+        // Drop the oldest messages to keep the channel within its cap:
+        while !messages.is_empty() && messages.len() >= max_messages {
+          messages.remove(0);
         }
-      }
-
-      // Update this blog only if at lest one field should be updated:
-      if fields_updated > 0 {
-        blog.updated = Some(Self::new_change(owner.clone()));
-        <BlogById<T>>::insert(blog_id, blog);
-        Self::deposit_event(RawEvent::BlogUpdated(owner.clone(), blog_id));
-      }
+        messages.push(message);
+      });
+      <NextChannelMessageId<T>>::insert(blog_id, message_id + 1);
+      Self::deposit_event(RawEvent::ChannelMessagePosted(owner.clone(), blog_id, message_id));
     }
-    
-    fn update_post(origin, post_id: T::PostId, update: PostUpdate<T>) {
+
+    fn delete_channel_message(origin, blog_id: T::BlogId, message_id: u64) {
       let owner = ensure_signed(origin)?;
-      
-      let has_updates = 
-        update.blog_id.is_some() ||
-        update.slug.is_some() ||
-        update.json.is_some();
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
 
-      ensure!(has_updates, "Nothing to update in a post");
+      ensure!(Self::can_moderate_blog(&owner, blog_id), "Only a blog owner or a blog moderator can delete channel messages");
 
-      let mut post = Self::post_by_id(post_id).ok_or("Post was not found by id")?;
+      let mut messages = Self::channel_messages_by_blog_id(blog_id);
+      let index = messages.iter().position(|m| m.id == message_id).ok_or("Channel message was not found by id")?;
+      messages.remove(index);
 
-      // TODO ensure: blog writers also should be able to edit this post:
-      ensure!(owner == post.created.account, "Only a post owner can update their post");
+      <ChannelMessagesByBlogId<T>>::insert(blog_id, messages);
+      Self::deposit_event(RawEvent::ChannelMessageDeleted(owner.clone(), blog_id, message_id));
+    }
 
-      let mut fields_updated = 0;
+    fn claim_expired_slug(origin, blog_id: T::BlogId, slug: Vec<u8>) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
 
-      if let Some(slug) = update.slug {
-        if slug != post.slug {
-          // TODO validate slug.
-          ensure!(!<PostIdBySlug<T>>::exists(slug.clone()), "Post slug is not unique");
-          <PostIdBySlug<T>>::remove(post.slug);
-          <PostIdBySlug<T>>::insert(slug.clone(), post_id);
-          post.slug = slug;
-          fields_updated += 1;
-        }
-      }
+      let mut blog = Self::blog_by_id(blog_id).ok_or("Blog was not found by id")?;
+      ensure!(owner == blog.created.account, "Only a blog owner can change a slug of their blog");
 
-      if let Some(json) = update.json {
-        if json != post.json {
-          // TODO validate json.
-          post.json = json;
-          fields_updated += 1;
-        }
-      }
+      let (_, expires_at) = Self::slug_redirect(slug.clone()).ok_or("There is no redirect for this slug")?;
+      ensure!(expires_at <= <system::Module<T>>::block_number(), "Slug redirect has not expired yet");
+      Self::ensure_blog_slug_is_valid(&slug)?;
+      Self::ensure_slug_change_is_not_throttled(blog_id)?;
 
-      // Move this post to another blog:
-      if let Some(blog_id) = update.blog_id {
-        if blog_id != post.blog_id {
-          Self::ensure_blog_exists(blog_id)?;
-          
-          // Remove post_id from its old blog:
-          <PostIdsByBlogId<T>>::mutate(post.blog_id, |post_ids| {
-            if let Some(index) = post_ids.iter().position(|x| *x == post_id) {
-              post_ids.swap_remove(index);
-            }
-          });
-          
-          // Add post_id to its new blog:
-          <PostIdsByBlogId<T>>::mutate(blog_id.clone(), |ids| ids.push(post_id));
-          post.blog_id = blog_id;
-          fields_updated += 1;
-        }
-      }
+      Self::change_blog_slug(&mut blog, slug);
+      blog.updated = Some(Self::new_change(owner.clone()));
+      <BlogById<T>>::insert(blog_id, blog);
+      Self::deposit_event(RawEvent::BlogUpdated(owner.clone(), blog_id));
+    }
 
-      // Update this post only if at lest one field should be updated:
-      if fields_updated > 0 {
-        post.updated = Some(Self::new_change(owner.clone()));
-        <PostById<T>>::insert(post_id, post);
-        Self::deposit_event(RawEvent::PostUpdated(owner.clone(), post_id));
-      }
+    fn verify_blog(origin, blog_id: T::BlogId) {
+      let authority = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&authority)?;
+      Self::ensure_verification_authority(&authority)?;
+
+      let mut blog = Self::blog_by_id(blog_id).ok_or("Blog was not found by id")?;
+      ensure!(blog.verified.is_none(), "Blog is already verified");
+
+      blog.verified = Some(Self::new_change(authority.clone()));
+      <BlogById<T>>::insert(blog_id, blog);
+      Self::deposit_event(RawEvent::BlogVerified(authority, blog_id));
     }
-    
-    fn update_comment(origin, comment_id: T::CommentId, update: CommentUpdate) {
-      let owner = ensure_signed(origin)?;
 
-      let mut comment = Self::comment_by_id(comment_id).ok_or("Comment was not found by id")?;
-      ensure!(owner == comment.created.account, "Only comment author can update their comment");
+    fn unverify_blog(origin, blog_id: T::BlogId) {
+      let authority = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&authority)?;
+      Self::ensure_verification_authority(&authority)?;
 
-      let json = update.json;
-      // TODO validate min length
-      ensure!(json.len() <= Self::comment_max_len() as usize, "Comment JSON is too long");
-      ensure!(json != comment.json, "New comment JSON is the same as old one");
+      let mut blog = Self::blog_by_id(blog_id).ok_or("Blog was not found by id")?;
+      ensure!(blog.verified.is_some(), "Blog is not verified");
 
-      comment.json = json;
-      comment.updated = Some(Self::new_change(owner.clone()));
-      <CommentById<T>>::insert(comment_id, comment);
-      Self::deposit_event(RawEvent::CommentUpdated(owner.clone(), comment_id));
+      blog.verified = None;
+      <BlogById<T>>::insert(blog_id, blog);
+      Self::deposit_event(RawEvent::BlogUnverified(authority, blog_id));
     }
 
-    fn update_post_reaction(origin, post_id: T::PostId, reaction_id: T::ReactionId, new_kind: ReactionKind) {
+    fn set_verification_authority(authority: T::AccountId) {
+      <VerificationAuthority<T>>::put(authority);
+    }
+
+    fn grant_blog_role(origin, blog_id: T::BlogId, account: T::AccountId, role: BlogRole) {
       let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
 
+      let blog = Self::blog_by_id(blog_id).ok_or("Blog was not found by id")?;
+      ensure!(owner == blog.created.account, "Only a blog owner can grant roles in their blog");
+      ensure!(account != blog.created.account, "Blog owner already has all permissions in their blog");
       ensure!(
-        <PostReactionIdByAccount<T>>::exists((owner.clone(), post_id)),
-        "Account has not reacted to this post yet. Use create_post_reaction()"
+        Self::role_by_blog_and_account((blog_id, account.clone())) != Some(role),
+        "Account already has this role in the blog"
       );
 
-      let mut reaction = Self::reaction_by_id(reaction_id).ok_or("Reaction was not found by id")?;
-      ensure!(owner == reaction.created.account, "Only reaction owner can update their reaction");
-      ensure!(reaction.kind != new_kind, "Current account reaction is the same as requested");
+      if !<RoleByBlogAndAccount<T>>::exists((blog_id, account.clone())) {
+        <BlogIdsByRoleHolder<T>>::mutate(account.clone(), |ids| ids.push(blog_id));
+      }
+      <RoleByBlogAndAccount<T>>::insert((blog_id, account.clone()), role);
+      Self::deposit_event(RawEvent::BlogRoleGranted(owner.clone(), blog_id, account, role));
+    }
 
-      reaction.kind = new_kind;
-      reaction.updated = Some(Self::new_change(owner.clone()));
-      <ReactionById<T>>::insert(reaction_id, reaction);
+    fn revoke_blog_role(origin, blog_id: T::BlogId, account: T::AccountId) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
 
-      let mut post = Self::post_by_id(post_id).ok_or("Post was not found by id")?;
-      match new_kind {
-        ReactionKind::Upvote => {
-          post.upvotes_count += 1;
-          post.downvotes_count -= 1;
-        },
-        ReactionKind::Downvote => {
-          post.downvotes_count += 1;
-          post.upvotes_count -= 1;
-        },
+      let blog = Self::blog_by_id(blog_id).ok_or("Blog was not found by id")?;
+      ensure!(owner == blog.created.account, "Only a blog owner can revoke roles in their blog");
+
+      let role = Self::role_by_blog_and_account((blog_id, account.clone())).ok_or("Account has no role in the blog")?;
+
+      <RoleByBlogAndAccount<T>>::remove((blog_id, account.clone()));
+      <BlogIdsByRoleHolder<T>>::mutate(account.clone(), |ids| {
+        if let Some(index) = ids.iter().position(|x| *x == blog_id) {
+          ids.swap_remove(index);
+        }
+      });
+      Self::deposit_event(RawEvent::BlogRoleRevoked(owner.clone(), blog_id, account, role));
+    }
+
+    // Existing reactions keep their indices, so labels can only be appended to the current ones.
+    fn set_custom_reactions(origin, blog_id: T::BlogId, labels: Vec<Vec<u8>>) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let blog = Self::blog_by_id(blog_id).ok_or("Blog was not found by id")?;
+      ensure!(owner == blog.created.account, "Only a blog owner can set custom reactions of their blog");
+      let max_custom_reactions = Self::follower_tier(blog_id)
+        .map_or(Self::max_custom_reactions_per_blog(), |tier| tier.max_custom_reactions);
+      ensure!(labels.len() <= max_custom_reactions as usize, "Too many custom reactions");
+      ensure!(labels.len() <= u8::max_value() as usize + 1, "Blog cannot have more than 256 custom reactions");
+      ensure!(
+        labels.starts_with(&Self::custom_reactions_by_blog_id(blog_id)),
+        "Custom reactions can only be appended to the current ones"
+      );
+
+      let label_max_len = Self::custom_reaction_max_len() as usize;
+      for label in labels.iter() {
+        ensure!(!label.is_empty(), "Custom reaction label cannot be empty");
+        ensure!(label.len() <= label_max_len, "Custom reaction label is too long");
       }
-      // TODO maybe use mutate instead of insert?
-      <PostById<T>>::insert(post_id, post);
 
-      Self::deposit_event(RawEvent::PostReactionUpdated(owner.clone(), post_id, reaction_id));
+      <CustomReactionsByBlogId<T>>::insert(blog_id, labels);
+      Self::deposit_event(RawEvent::CustomReactionsUpdated(owner, blog_id));
     }
 
-    fn update_comment_reaction(origin, comment_id: T::CommentId, reaction_id: T::ReactionId, new_kind: ReactionKind) {
+    // The creator becomes the first member of a new org, with a threshold of one approval.
+    fn create_org(origin) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+      ensure!(!<OrgIdByAccount<T>>::exists(&owner), "Org account cannot create an org");
+
+      let org_id = Self::next_org_id();
+      let account = Self::org_account(org_id);
+      <OrgById<T>>::insert(org_id, Org {
+        id: org_id,
+        created: Self::new_change(owner.clone()),
+        account: account.clone(),
+        members: vec![owner.clone()],
+        threshold: 1,
+      });
+      <OrgIdByAccount<T>>::insert(account, org_id);
+      <OrgIdsByMember<T>>::mutate(owner.clone(), |ids| ids.push(org_id));
+      <NextOrgId<T>>::mutate(|n| { *n += 1; });
+      Self::deposit_event(RawEvent::OrgCreated(owner, org_id));
+    }
+
+    // Can be called only by the org account, i.e. through an approved org proposal.
+    fn add_org_member(origin, org_id: u64, member: T::AccountId) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+
+      let mut org = Self::org_by_id(org_id).ok_or("Org was not found by id")?;
+      ensure!(owner == org.account, "Only an org account can add members to the org");
+      ensure!(!org.members.contains(&member), "Account is already a member of this org");
+      ensure!(member != org.account, "Org account cannot be a member of its org");
+      ensure!(org.members.len() < Self::max_org_members() as usize, "Org has reached the max number of members");
+
+      org.members.push(member.clone());
+      <OrgById<T>>::insert(org_id, org);
+      <OrgIdsByMember<T>>::mutate(member.clone(), |ids| ids.push(org_id));
+      Self::deposit_event(RawEvent::OrgMemberAdded(owner, org_id, member));
+    }
+
+    // Can be called only by the org account, i.e. through an approved org proposal.
+    // The threshold should be lowered first if it equals the number of members.
+    fn remove_org_member(origin, org_id: u64, member: T::AccountId) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+
+      let mut org = Self::org_by_id(org_id).ok_or("Org was not found by id")?;
+      ensure!(owner == org.account, "Only an org account can remove members of the org");
+      let index = org.members.iter().position(|x| *x == member).ok_or("Account is not a member of this org")?;
+      ensure!(org.members.len() > org.threshold as usize, "Org would have fewer members than its threshold");
+
+      org.members.remove(index);
+      <OrgById<T>>::insert(org_id, org);
+      <OrgIdsByMember<T>>::mutate(member.clone(), |ids| {
+        if let Some(index) = ids.iter().position(|x| *x == org_id) {
+          ids.swap_remove(index);
+        }
+      });
+      Self::deposit_event(RawEvent::OrgMemberRemoved(owner, org_id, member));
+    }
+
+    // Can be called only by the org account, i.e. through an approved org proposal.
+    fn set_org_threshold(origin, org_id: u64, threshold: u16) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+
+      let mut org = Self::org_by_id(org_id).ok_or("Org was not found by id")?;
+      ensure!(owner == org.account, "Only an org account can change its threshold");
+      ensure!(threshold > 0, "Org threshold should be greater than zero");
+      ensure!(threshold as usize <= org.members.len(), "Org threshold cannot exceed the number of members");
+      ensure!(threshold != org.threshold, "Org already has this threshold");
+
+      org.threshold = threshold;
+      <OrgById<T>>::insert(org_id, org);
+      Self::deposit_event(RawEvent::OrgThresholdChanged(owner, org_id, threshold));
+    }
+
+    // Propose a call of this module, e.g. create_blog() or create_post(), to be signed by the org account.
+    // The proposal counts as approved by the proposer. If that meets the threshold, the call is dispatched at once.
+    fn propose_org_call(origin, org_id: u64, call: Box<Call<T>>) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let org = Self::org_by_id(org_id).ok_or("Org was not found by id")?;
+      ensure!(org.members.contains(&owner), "Only an org member can propose calls of the org");
+
+      if org.threshold <= 1 {
+        call.dispatch(system::RawOrigin::Signed(org.account).into())?;
+        Self::deposit_event(RawEvent::OrgCallDispatched(owner, org_id));
+      } else {
+        ensure!(
+          Self::pending_proposal_ids_by_org_id(org_id).len() < Self::max_pending_proposals_per_org() as usize,
+          "Org has too many pending proposals"
+        );
+
+        let proposal_id = Self::next_org_proposal_id();
+        <OrgProposalById<T>>::insert(proposal_id, OrgProposal {
+          id: proposal_id,
+          org_id,
+          created: Self::new_change(owner.clone()),
+          call: *call,
+          approvals: vec![owner.clone()],
+        });
+        <PendingProposalIdsByOrgId<T>>::mutate(org_id, |ids| ids.push(proposal_id));
+        <NextOrgProposalId<T>>::mutate(|n| { *n += 1; });
+        Self::deposit_event(RawEvent::OrgCallProposed(owner, org_id, proposal_id));
+      }
+    }
+
+    // Only approvals of current members are counted. If the dispatched call fails,
+    // this approval is not recorded and the proposal stays pending.
+    fn approve_org_call(origin, proposal_id: u64) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let mut proposal = Self::org_proposal_by_id(proposal_id).ok_or("Org proposal was not found by id")?;
+      let org = Self::org_by_id(proposal.org_id).ok_or("Org was not found by id")?;
+      ensure!(org.members.contains(&owner), "Only an org member can approve calls of the org");
+      ensure!(!proposal.approvals.contains(&owner), "Account has already approved this proposal");
+
+      proposal.approvals.push(owner.clone());
+      let approvals_count = proposal.approvals.iter().filter(|a| org.members.contains(a)).count();
+
+      if approvals_count >= org.threshold as usize {
+        proposal.call.clone().dispatch(system::RawOrigin::Signed(org.account).into())?;
+        Self::remove_org_proposal(&proposal);
+        Self::deposit_event(RawEvent::OrgCallDispatched(owner, org.id));
+      } else {
+        <OrgProposalById<T>>::insert(proposal_id, proposal);
+        Self::deposit_event(RawEvent::OrgCallApproved(owner, proposal_id));
+      }
+    }
+
+    // A proposer can cancel their proposal while it is pending.
+    fn cancel_org_proposal(origin, proposal_id: u64) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let proposal = Self::org_proposal_by_id(proposal_id).ok_or("Org proposal was not found by id")?;
+      ensure!(owner == proposal.created.account, "Only a proposer can cancel their org proposal");
+
+      Self::remove_org_proposal(&proposal);
+      Self::deposit_event(RawEvent::OrgProposalCancelled(owner, proposal_id));
+    }
+
+    fn set_blog_topics(origin, blog_id: T::BlogId, topics: Vec<TopicId>) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let blog = Self::blog_by_id(blog_id).ok_or("Blog was not found by id")?;
+      Self::ensure_blog_is_active(&blog)?;
+      ensure!(owner == blog.created.account, "Only a blog owner can set topics of their blog");
+      ensure!(topics.len() <= Self::max_topics_per_blog() as usize, "Too many topics");
+
+      let mut new_topics = topics;
+      new_topics.sort();
+      new_topics.dedup();
+
+      let old_topics = Self::topics_by_blog_id(blog_id);
+      ensure!(new_topics != old_topics, "Blog already has these topics");
+
+      for topic in old_topics.iter().filter(|t| !new_topics.contains(t)) {
+        <BlogIdsByTopic<T>>::mutate(topic, |ids| {
+          if let Some(index) = ids.iter().position(|x| *x == blog_id) {
+            ids.swap_remove(index);
+          }
+        });
+      }
+      for topic in new_topics.iter().filter(|t| !old_topics.contains(t)) {
+        <BlogIdsByTopic<T>>::mutate(topic, |ids| ids.push(blog_id));
+      }
+
+      <TopicsByBlogId<T>>::insert(blog_id, new_topics);
+      Self::deposit_event(RawEvent::BlogTopicsUpdated(owner, blog_id));
+    }
+
+    // Posts of all blogs with a followed topic get into the feed of an account.
+    fn follow_topic(origin, topic: TopicId) {
       let owner = ensure_signed(origin)?;
+      Self::ensure_call_group_is_not_paused(CallGroup::Following)?;
+      Self::ensure_account_is_not_erased(&owner)?;
 
+      let mut topics = Self::topics_followed_by_account(owner.clone());
+      ensure!(!topics.contains(&topic), "Account is already following this topic");
       ensure!(
-        <CommentReactionIdByAccount<T>>::exists((owner.clone(), comment_id)),
-        "Account has not reacted to this comment yet. Use create_comment_reaction()"
+        topics.len() < Self::max_topics_followed_by_account() as usize,
+        "Account has reached the max number of followed topics"
       );
 
-      let mut reaction = Self::reaction_by_id(reaction_id).ok_or("Reaction was not found by id")?;
-      ensure!(owner == reaction.created.account, "Only reaction owner can update their reaction");
-      ensure!(reaction.kind != new_kind, "Current account reaction is the same as requested");
+      topics.push(topic);
+      <TopicsFollowedByAccount<T>>::insert(owner.clone(), topics);
+      <AccountsFollowingTopic<T>>::mutate(topic, |accounts| accounts.push(owner.clone()));
+      Self::deposit_event(RawEvent::TopicFollowed(owner, topic));
+    }
 
-      reaction.kind = new_kind;
-      reaction.updated = Some(Self::new_change(owner.clone()));
-      <ReactionById<T>>::insert(reaction_id, reaction);
+    fn unfollow_topic(origin, topic: TopicId) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_call_group_is_not_paused(CallGroup::Following)?;
+      Self::ensure_account_is_not_erased(&owner)?;
 
-      let mut comment = Self::comment_by_id(comment_id).ok_or("Comment was not found by id")?;
-      match new_kind {
-        ReactionKind::Upvote => {
-          comment.upvotes_count += 1;
-          comment.downvotes_count -= 1;
-        },
-        ReactionKind::Downvote => {
-          comment.downvotes_count += 1;
-          comment.upvotes_count -= 1;
-        },
+      let mut topics = Self::topics_followed_by_account(owner.clone());
+      let index = topics.iter().position(|t| *t == topic).ok_or("Account is not following this topic")?;
+
+      topics.swap_remove(index);
+      <TopicsFollowedByAccount<T>>::insert(owner.clone(), topics);
+      Self::remove_topic_follower(&owner, topic);
+      Self::deposit_event(RawEvent::TopicUnfollowed(owner, topic));
+    }
+
+    fn deposit_to_fee_pot(origin, blog_id: T::BlogId, amount: BalanceOf<T>) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let blog = Self::blog_by_id(blog_id).ok_or("Blog was not found by id")?;
+      ensure!(owner == blog.created.account, "Only a blog owner can deposit to a fee pot of their blog");
+      ensure!(!amount.is_zero(), "Deposit should be greater than zero");
+
+      T::Currency::reserve(&owner, amount)?;
+      <FeePotByBlogId<T>>::mutate(blog_id, |pot| *pot += amount);
+      Self::deposit_event(RawEvent::FeePotDeposited(owner, blog_id, amount));
+    }
+
+    fn withdraw_from_fee_pot(origin, blog_id: T::BlogId, amount: BalanceOf<T>) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let blog = Self::blog_by_id(blog_id).ok_or("Blog was not found by id")?;
+      ensure!(owner == blog.created.account, "Only a blog owner can withdraw from a fee pot of their blog");
+      ensure!(!amount.is_zero(), "Withdrawal should be greater than zero");
+      ensure!(amount <= Self::fee_pot_by_blog_id(blog_id), "Fee pot has not enough funds");
+
+      T::Currency::unreserve(&owner, amount);
+      <FeePotByBlogId<T>>::mutate(blog_id, |pot| *pot -= amount);
+      Self::deposit_event(RawEvent::FeePotWithdrawn(owner, blog_id, amount));
+    }
+
+    // Zero fee lets anyone who can post to the blog do it for free.
+    fn set_posting_fee(origin, blog_id: T::BlogId, fee: BalanceOf<T>) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let blog = Self::blog_by_id(blog_id).ok_or("Blog was not found by id")?;
+      ensure!(owner == blog.created.account, "Only a blog owner can set a posting fee of their blog");
+      ensure!(fee != Self::posting_fee_by_blog_id(blog_id), "Blog already has this posting fee");
+
+      if fee.is_zero() {
+        <PostingFeeByBlogId<T>>::remove(blog_id);
+      } else {
+        <PostingFeeByBlogId<T>>::insert(blog_id, fee);
       }
-      // TODO maybe use mutate instead of insert?
-      <CommentById<T>>::insert(comment_id, comment);
+      Self::deposit_event(RawEvent::PostingFeeSet(owner, blog_id, fee));
+    }
+
+    // A blog owner accepts a post to their blog and the reserved posting fee is returned to the payer.
+    fn accept_post(origin, post_id: T::PostId) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let fee = Self::posting_fee_paid_by_post_id(post_id).ok_or("There is no posting fee to refund for this post")?;
+      ensure!(Self::is_blog_owner(&owner, fee.blog_id), "Only a blog owner can accept posts to their blog");
+      ensure!(<system::Module<T>>::block_number() < fee.release_at, "Posting fee hold period has ended");
+
+      T::Currency::unreserve(&fee.payer, fee.amount);
+      <PostingFeePaidByPostId<T>>::remove(post_id);
+      Self::deposit_event(RawEvent::PostAccepted(owner, post_id, fee.amount));
+    }
+
+    // Anyone can move a posting fee of a post that was not accepted in time to the blog owner.
+    // If the blog has been handed over to the tombstone account, the fee is returned to the payer instead.
+    fn collect_posting_fee(origin, post_id: T::PostId) {
+      let _ = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+
+      let fee = Self::posting_fee_paid_by_post_id(post_id).ok_or("There is no posting fee to collect for this post")?;
+      ensure!(<system::Module<T>>::block_number() >= fee.release_at, "Posting fee hold period has not ended yet");
+      let blog = Self::blog_by_id(fee.blog_id).ok_or("Blog was not found by id")?;
+      let blog_owner = blog.created.account;
+
+      if Self::tombstone_account() == Some(blog_owner.clone()) {
+        T::Currency::unreserve(&fee.payer, fee.amount);
+      } else {
+        // The returned balance is what could not be moved from the reserve of the payer:
+        let remainder = T::Currency::repatriate_reserved(&fee.payer, &blog_owner, fee.amount)?;
+        Self::deposit_event(RawEvent::PostingFeeCollected(blog_owner, post_id, fee.amount - remainder));
+      }
+      <PostingFeePaidByPostId<T>>::remove(post_id);
+    }
+
+    fn set_membership_tiers(origin, blog_id: T::BlogId, tiers: Vec<MembershipTier<T>>) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let blog = Self::blog_by_id(blog_id).ok_or("Blog was not found by id")?;
+      ensure!(owner == blog.created.account, "Only a blog owner can set membership tiers of their blog");
+      ensure!(tiers.len() <= Self::max_membership_tiers_per_blog() as usize, "Too many membership tiers");
+
+      let name_max_len = Self::membership_tier_name_max_len() as usize;
+      for tier in tiers.iter() {
+        ensure!(!tier.name.is_empty(), "Membership tier name cannot be empty");
+        ensure!(tier.name.len() <= name_max_len, "Membership tier name is too long");
+        ensure!(!tier.duration.is_zero(), "Membership tier duration should be greater than zero");
+      }
+
+      <MembershipTiersByBlogId<T>>::insert(blog_id, tiers);
+      Self::deposit_event(RawEvent::MembershipTiersUpdated(owner, blog_id));
+    }
+
+    // The price of a tier is transferred to the blog owner.
+    fn buy_membership(origin, blog_id: T::BlogId, tier: u16) {
+      let buyer = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&buyer)?;
+
+      let blog = Self::blog_by_id(blog_id).ok_or("Blog was not found by id")?;
+      Self::ensure_blog_is_active(&blog)?;
+      let tiers = Self::membership_tiers_by_blog_id(blog_id);
+      let membership_tier = tiers.get(tier as usize).ok_or("Membership tier was not found by index")?;
+
+      T::Currency::transfer(&buyer, &blog.created.account, membership_tier.price)?;
+
+      let membership_id = Self::next_membership_id();
+      let membership: BlogMembership<T> = BlogMembership {
+        id: membership_id,
+        blog_id,
+        tier,
+        owner: buyer.clone(),
+        expires_at: <system::Module<T>>::block_number() + membership_tier.duration,
+      };
+
+      <MembershipById<T>>::insert(membership_id, membership);
+      <MembershipIdsByAccount<T>>::mutate(buyer.clone(), |ids| ids.push(membership_id));
+      <NextMembershipId<T>>::mutate(|n| { *n += 1; });
+      Self::deposit_event(RawEvent::MembershipBought(buyer, blog_id, membership_id));
+    }
+
+    fn transfer_membership(origin, membership_id: u64, to: T::AccountId) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let mut membership = Self::membership_by_id(membership_id).ok_or("Membership was not found by id")?;
+      ensure!(owner == membership.owner, "Only a membership owner can transfer their membership");
+      ensure!(owner != to, "Cannot transfer a membership to its owner");
+      ensure!(
+        <system::Module<T>>::block_number() < membership.expires_at,
+        "Expired membership cannot be transferred"
+      );
+
+      <MembershipIdsByAccount<T>>::mutate(owner.clone(), |ids| {
+        if let Some(index) = ids.iter().position(|x| *x == membership_id) {
+          ids.swap_remove(index);
+        }
+      });
+      <MembershipIdsByAccount<T>>::mutate(to.clone(), |ids| ids.push(membership_id));
+
+      membership.owner = to.clone();
+      <MembershipById<T>>::insert(membership_id, membership);
+      Self::deposit_event(RawEvent::MembershipTransferred(owner, to, membership_id));
+    }
+
+    fn invite_writer(origin, blog_id: T::BlogId, account: T::AccountId) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let blog = Self::blog_by_id(blog_id).ok_or("Blog was not found by id")?;
+      Self::ensure_blog_is_active(&blog)?;
+      ensure!(owner == blog.created.account, "Only a blog owner can invite writers to their blog");
+      ensure!(account != blog.created.account, "Blog owner cannot be invited as a writer");
+      ensure!(!blog.writers.contains(&account), "Account is already a writer of this blog");
+      ensure!(blog.writers.len() < Self::max_blog_writers() as usize, "Blog has reached the max number of writers");
+
+      let now = <system::Module<T>>::block_number();
+      if let Some(expires_at) = Self::writer_invite_by_blog_and_account((blog_id, account.clone())) {
+        ensure!(expires_at <= now, "Account has already been invited to this blog");
+      }
+
+      <WriterInviteByBlogAndAccount<T>>::insert((blog_id, account.clone()), now + Self::writer_invite_period());
+      <InvitedBlogIdsByAccount<T>>::mutate(account.clone(), |ids| {
+        if !ids.contains(&blog_id) {
+          ids.push(blog_id);
+        }
+      });
+      Self::deposit_event(RawEvent::WriterInvited(owner.clone(), blog_id, account));
+    }
+
+    fn accept_writer_invite(origin, blog_id: T::BlogId) {
+      let writer = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&writer)?;
+
+      let expires_at = Self::writer_invite_by_blog_and_account((blog_id, writer.clone()))
+        .ok_or("There is no writer invite to this blog")?;
+      ensure!(<system::Module<T>>::block_number() < expires_at, "Writer invite has expired");
+
+      let mut blog = Self::blog_by_id(blog_id).ok_or("Blog was not found by id")?;
+      Self::ensure_blog_is_active(&blog)?;
+      ensure!(
+        Self::tombstone_account() != Some(blog.created.account.clone()),
+        "Blog of an erased account cannot get new writers"
+      );
+
+      if !blog.writers.contains(&writer) {
+        // More accounts can be invited than there are free writer places left:
+        ensure!(blog.writers.len() < Self::max_blog_writers() as usize, "Blog has reached the max number of writers");
+        blog.writers.push(writer.clone());
+        <BlogById<T>>::insert(blog_id, blog);
+        <BlogIdsByWriter<T>>::mutate(writer.clone(), |ids| ids.push(blog_id));
+      }
+      Self::remove_writer_invite(blog_id, &writer);
+      Self::deposit_event(RawEvent::WriterInviteAccepted(writer, blog_id));
+    }
+
+    fn decline_writer_invite(origin, blog_id: T::BlogId) {
+      let writer = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&writer)?;
+
+      ensure!(
+        <WriterInviteByBlogAndAccount<T>>::exists((blog_id, writer.clone())),
+        "There is no writer invite to this blog"
+      );
+
+      Self::remove_writer_invite(blog_id, &writer);
+      Self::deposit_event(RawEvent::WriterInviteDeclined(writer, blog_id));
+    }
+
+    fn create_series(origin, blog_id: T::BlogId, ipfs_hash: Vec<u8>) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let blog = Self::blog_by_id(blog_id).ok_or("Blog was not found by id")?;
+      Self::ensure_blog_is_active(&blog)?;
+      ensure!(Self::can_create_post(&owner, &blog), "Account has no permission to create series in this blog");
+      ensure!(!ipfs_hash.is_empty(), "Series IPFS hash cannot be empty");
+      ensure!(ipfs_hash.len() <= Self::ipfs_hash_max_len() as usize, "Series IPFS hash is too long");
+
+      let series_id = Self::next_series_id();
+      let new_series: PostSeries<T> = PostSeries {
+        id: series_id,
+        blog_id,
+        created: Self::new_change(owner.clone()),
+        updated: None,
+        ipfs_hash,
+      };
+
+      <SeriesById<T>>::insert(series_id, new_series);
+      <SeriesIdsByBlogId<T>>::mutate(blog_id, |ids| ids.push(series_id));
+      <SeriesIdsByAccount<T>>::mutate(owner.clone(), |ids| ids.push(series_id));
+      <NextSeriesId<T>>::mutate(|n| { *n += 1; });
+      Self::deposit_event(RawEvent::SeriesCreated(owner, series_id));
+    }
+
+    fn add_post_to_series(origin, series_id: u64, post_id: T::PostId, position: u32) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let series = Self::series_by_id(series_id).ok_or("Series was not found by id")?;
+      Self::ensure_can_manage_series(&owner, &series)?;
+
+      let post = Self::post_by_id(post_id).ok_or("Post was not found by id")?;
+      ensure!(post.blog_id == series.blog_id, "Post and series should belong to the same blog");
+
+      let mut post_ids = Self::post_ids_by_series_id(series_id);
+      ensure!(!post_ids.contains(&post_id), "Post is already in this series");
+      ensure!(post_ids.len() < Self::max_posts_per_series() as usize, "Series has reached the max number of posts");
+      ensure!(position as usize <= post_ids.len(), "Position is out of the series bounds");
+
+      post_ids.insert(position as usize, post_id);
+      Self::update_series_posts(owner, series, post_ids);
+    }
+
+    fn move_post_in_series(origin, series_id: u64, post_id: T::PostId, new_position: u32) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let series = Self::series_by_id(series_id).ok_or("Series was not found by id")?;
+      Self::ensure_can_manage_series(&owner, &series)?;
+
+      let mut post_ids = Self::post_ids_by_series_id(series_id);
+      let index = post_ids.iter().position(|x| *x == post_id).ok_or("Post is not in this series")?;
+      ensure!((new_position as usize) < post_ids.len(), "Position is out of the series bounds");
+      ensure!(index != new_position as usize, "Post is already at this position");
+
+      post_ids.remove(index);
+      post_ids.insert(new_position as usize, post_id);
+      Self::update_series_posts(owner, series, post_ids);
+    }
+
+    fn remove_post_from_series(origin, series_id: u64, post_id: T::PostId) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let series = Self::series_by_id(series_id).ok_or("Series was not found by id")?;
+      Self::ensure_can_manage_series(&owner, &series)?;
+
+      let mut post_ids = Self::post_ids_by_series_id(series_id);
+      let index = post_ids.iter().position(|x| *x == post_id).ok_or("Post is not in this series")?;
+
+      post_ids.remove(index);
+      Self::update_series_posts(owner, series, post_ids);
+    }
+
+    fn create_list(origin, name: Vec<u8>, is_public: bool) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      ensure!(!name.is_empty(), "List name cannot be empty");
+      ensure!(name.len() <= Self::list_name_max_len() as usize, "List name is too long");
+      ensure!(
+        Self::lists_by_account(owner.clone()).len() < Self::max_lists_per_account() as usize,
+        "Account has reached the max number of lists"
+      );
+
+      let list_id = Self::next_blog_list_id();
+      let new_list: BlogList<T> = BlogList {
+        id: list_id,
+        created: Self::new_change(owner.clone()),
+        updated: None,
+        name,
+        is_public,
+        blogs_count: 0
+      };
+
+      <BlogListById<T>>::insert(list_id, new_list);
+      <ListsByAccount<T>>::mutate(owner.clone(), |ids| ids.push(list_id));
+      <NextBlogListId<T>>::mutate(|n| { *n += T::BlogListId::sa(1); });
+      Self::deposit_event(RawEvent::BlogListCreated(owner.clone(), list_id));
+    }
+
+    fn update_list(origin, list_id: T::BlogListId, name: Option<Vec<u8>>, is_public: Option<bool>) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      ensure!(name.is_some() || is_public.is_some(), "Nothing to update in a list");
+
+      let mut list = Self::blog_list_by_id(list_id).ok_or("List was not found by id")?;
+      ensure!(owner == list.created.account, "Only a list owner can update their list");
+
+      let mut fields_updated = 0;
+
+      if let Some(name) = name {
+        if name != list.name {
+          ensure!(!name.is_empty(), "List name cannot be empty");
+          ensure!(name.len() <= Self::list_name_max_len() as usize, "List name is too long");
+          list.name = name;
+          fields_updated += 1;
+        }
+      }
+
+      if let Some(is_public) = is_public {
+        if is_public != list.is_public {
+          list.is_public = is_public;
+          fields_updated += 1;
+        }
+      }
+
+      // Update this list only if at lest one field should be updated:
+      if fields_updated > 0 {
+        list.updated = Some(Self::new_change(owner.clone()));
+        <BlogListById<T>>::insert(list_id, list);
+        Self::deposit_event(RawEvent::BlogListUpdated(owner.clone(), list_id));
+      }
+    }
+
+    fn add_blog_to_list(origin, list_id: T::BlogListId, blog_id: T::BlogId) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let mut list = Self::blog_list_by_id(list_id).ok_or("List was not found by id")?;
+      ensure!(owner == list.created.account, "Only a list owner can add blogs to their list");
+      Self::ensure_blog_exists(blog_id)?;
+
+      let blog_ids = Self::blog_ids_by_list(list_id);
+      ensure!(!blog_ids.contains(&blog_id), "Blog is already in this list");
+      ensure!(blog_ids.len() < Self::max_blogs_per_list() as usize, "List has reached the max number of blogs");
+
+      <BlogIdsByList<T>>::mutate(list_id, |ids| ids.push(blog_id));
+      list.blogs_count += 1;
+      <BlogListById<T>>::insert(list_id, list);
+      Self::deposit_event(RawEvent::BlogAddedToList(owner.clone(), list_id, blog_id));
+    }
+
+    fn remove_blog_from_list(origin, list_id: T::BlogListId, blog_id: T::BlogId) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let mut list = Self::blog_list_by_id(list_id).ok_or("List was not found by id")?;
+      ensure!(owner == list.created.account, "Only a list owner can remove blogs from their list");
+
+      let mut blog_ids = Self::blog_ids_by_list(list_id);
+      let index = blog_ids.iter().position(|x| *x == blog_id).ok_or("Blog is not in this list")?;
+      blog_ids.swap_remove(index);
+
+      <BlogIdsByList<T>>::insert(list_id, blog_ids);
+      list.blogs_count -= 1;
+      <BlogListById<T>>::insert(list_id, list);
+      Self::deposit_event(RawEvent::BlogRemovedFromList(owner.clone(), list_id, blog_id));
+    }
+
+    fn create_post(origin, blog_id: T::BlogId, slug: Vec<u8>, json: Vec<u8>, options: PostOptions<T>) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_call_group_is_not_paused(CallGroup::Posting)?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let PostOptions {
+        mentioned_accounts,
+        is_sponsored,
+        language,
+        post_as_blog,
+        license,
+        public_after,
+        comment_permission,
+        links_to,
+      } = options;
+
+      let blog = Self::blog_by_id(blog_id).ok_or("Blog was not found by id")?;
+      Self::ensure_blog_is_active(&blog)?;
+      ensure!(Self::can_create_post(&owner, &blog), "Account has no permission to create posts in this blog");
+
+      ensure!(slug.len() >= Self::slug_min_len() as usize, "Post slug is too short");
+      ensure!(slug.len() <= Self::slug_max_len() as usize, "Post slug is too long");
+      ensure!(!<PostIdBySlug<T>>::exists(slug.clone()), "Post slug is not unique");
+
+      ensure!(json.len() <= Self::post_max_len() as usize, "Post JSON is too long");
+      Self::ensure_blog_post_is_not_throttled(blog_id)?;
+      Self::ensure_language_is_valid(&language)?;
+      Self::ensure_license_is_valid(&license)?;
+      let links_to = Self::validate_links(None, links_to)?;
+      if let Some(public_after) = public_after {
+        ensure!(public_after > <system::Module<T>>::block_number(), "Post embargo should end in the future");
+        ensure!(
+          !Self::membership_tiers_by_blog_id(blog_id).is_empty(),
+          "Only a blog with membership tiers can embargo its posts"
+        );
+      }
+      Self::validate_mentions(&owner, &mentioned_accounts)?;
+      let mentioned_accounts = Self::filter_out_muting_accounts(mentioned_accounts, &json);
+
+      let posting_fee = if owner == blog.created.account { Zero::zero() } else { Self::posting_fee_by_blog_id(blog_id) };
+      let sponsored_post_fee = Self::sponsored_post_fee();
+      if is_sponsored {
+        ensure!(
+          T::Currency::can_slash(&owner, sponsored_post_fee + posting_fee),
+          "Not enough balance to pay for a sponsored post"
+        );
+      }
+      if !posting_fee.is_zero() {
+        T::Currency::reserve(&owner, posting_fee)?;
+      }
+      if is_sponsored {
+        let _ = T::Currency::slash(&owner, sponsored_post_fee);
+      }
+
+      // A post on behalf of a blog is authored by a blog account, while its writer is kept aside:
+      let author = if post_as_blog { Self::blog_account(blog_id) } else { owner.clone() };
+
+      let post_id = Self::next_post_id();
+      let mut new_post = Self::new_post(post_id, blog_id, author, slug, json);
+      new_post.is_sponsored = is_sponsored;
+      new_post.language = language;
+      new_post.license = license.clone();
+      new_post.public_after = public_after;
+      new_post.comment_permission = comment_permission;
+
+      if post_as_blog {
+        <WriterByPostId<T>>::insert(post_id, owner.clone());
+      }
+      Self::insert_new_post(blog, new_post);
+      if let Some(license) = license {
+        Self::add_license_to_history(post_id, license);
+      }
+      Self::add_mentions(mentioned_accounts, Mention::Post(post_id));
+      Self::update_links(post_id, links_to);
+      Self::deposit_event(RawEvent::PostCreated(owner.clone(), post_id));
+      if let Some(persona) = Self::active_persona_by_account(owner.clone()) {
+        <PersonaByPostId<T>>::insert(post_id, persona);
+        Self::deposit_event(RawEvent::PostCreatedAsPersona(owner.clone(), post_id, persona));
+      }
+      if is_sponsored {
+        Self::deposit_event(RawEvent::SponsoredPostCreated(owner.clone(), post_id, sponsored_post_fee));
+      }
+      if !posting_fee.is_zero() {
+        <PostingFeePaidByPostId<T>>::insert(post_id, PostingFee {
+          payer: owner.clone(),
+          blog_id,
+          amount: posting_fee,
+          release_at: <system::Module<T>>::block_number() + Self::posting_fee_hold_period(),
+        });
+        Self::deposit_event(RawEvent::PostingFeePaid(owner.clone(), post_id, posting_fee));
+      }
+    }
+
+    // Submit a post to a blog where the caller cannot post directly.
+    fn submit_post(origin, blog_id: T::BlogId, slug: Vec<u8>, json: Vec<u8>) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_call_group_is_not_paused(CallGroup::Posting)?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let blog = Self::blog_by_id(blog_id).ok_or("Blog was not found by id")?;
+      Self::ensure_blog_is_active(&blog)?;
+      ensure!(!Self::can_create_post(&owner, &blog), "Account can create posts in this blog directly");
+
+      ensure!(slug.len() >= Self::slug_min_len() as usize, "Post slug is too short");
+      ensure!(slug.len() <= Self::slug_max_len() as usize, "Post slug is too long");
+      ensure!(!<PostIdBySlug<T>>::exists(slug.clone()), "Post slug is not unique");
+      ensure!(json.len() <= Self::post_max_len() as usize, "Post JSON is too long");
+
+      let mut pending_ids = Self::pending_submission_ids_by_blog_id(blog_id);
+      ensure!(
+        pending_ids.len() < Self::max_pending_submissions_per_blog() as usize,
+        "Blog has reached the max number of pending submissions"
+      );
+
+      let deposit = Self::submission_deposit();
+      T::Currency::reserve(&owner, deposit)?;
+
+      let submission_id = Self::next_submission_id();
+      <SubmissionById<T>>::insert(submission_id, PostSubmission {
+        id: submission_id,
+        blog_id,
+        created: Self::new_change(owner.clone()),
+        slug,
+        json,
+        deposit,
+        expires_at: <system::Module<T>>::block_number() + Self::submission_period(),
+      });
+      pending_ids.push(submission_id);
+      <PendingSubmissionIdsByBlogId<T>>::insert(blog_id, pending_ids);
+      <PendingSubmissionIdsByAccount<T>>::mutate(owner.clone(), |ids| ids.push(submission_id));
+      <NextSubmissionId<T>>::mutate(|n| { *n += 1; });
+      Self::deposit_event(RawEvent::PostSubmitted(owner, blog_id, submission_id));
+    }
+
+    // Publish a submitted post on behalf of its author and return the deposit to them.
+    fn approve_submission(origin, submission_id: u64) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_call_group_is_not_paused(CallGroup::Posting)?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let submission = Self::submission_by_id(submission_id).ok_or("Submission was not found by id")?;
+      let blog_id = submission.blog_id;
+      let blog = Self::blog_by_id(blog_id).ok_or("Blog was not found by id")?;
+      Self::ensure_blog_is_active(&blog)?;
+      ensure!(owner == blog.created.account, "Only a blog owner can approve submissions to their blog");
+      ensure!(<system::Module<T>>::block_number() < submission.expires_at, "Submission has expired");
+      ensure!(!<PostIdBySlug<T>>::exists(submission.slug.clone()), "Post slug is not unique");
+
+      let author = submission.created.account.clone();
+      Self::ensure_account_is_not_erased(&author)?;
+      Self::ensure_blog_post_is_not_throttled(blog_id)?;
+
+      T::Currency::unreserve(&author, submission.deposit);
+      Self::remove_submission(&submission);
+
+      let post_id = Self::next_post_id();
+      let new_post = Self::new_post(post_id, blog_id, author.clone(), submission.slug, submission.json);
+      Self::insert_new_post(blog, new_post);
+      Self::deposit_event(RawEvent::PostCreated(author, post_id));
+      Self::deposit_event(RawEvent::SubmissionApproved(owner, submission_id, post_id));
+    }
+
+    // The deposit of a rejected submission is slashed to deter spam.
+    fn reject_submission(origin, submission_id: u64) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let submission = Self::submission_by_id(submission_id).ok_or("Submission was not found by id")?;
+      ensure!(
+        Self::is_blog_owner(&owner, submission.blog_id),
+        "Only a blog owner can reject submissions to their blog"
+      );
+
+      let _ = T::Currency::slash_reserved(&submission.created.account, submission.deposit);
+      Self::remove_submission(&submission);
+      Self::deposit_event(RawEvent::SubmissionRejected(owner, submission_id));
+    }
+
+    fn withdraw_submission(origin, submission_id: u64) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+
+      let submission = Self::submission_by_id(submission_id).ok_or("Submission was not found by id")?;
+      ensure!(owner == submission.created.account, "Only an author can withdraw their submission");
+
+      T::Currency::unreserve(&owner, submission.deposit);
+      Self::remove_submission(&submission);
+      Self::deposit_event(RawEvent::SubmissionWithdrawn(owner, submission_id));
+    }
+
+    // Anyone can clean up an expired submission. Its deposit is returned to the author.
+    fn expire_submission(origin, submission_id: u64) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+
+      let submission = Self::submission_by_id(submission_id).ok_or("Submission was not found by id")?;
+      ensure!(<system::Module<T>>::block_number() >= submission.expires_at, "Submission has not expired yet");
+
+      T::Currency::unreserve(&submission.created.account, submission.deposit);
+      Self::remove_submission(&submission);
+      Self::deposit_event(RawEvent::SubmissionExpired(owner, submission_id));
+    }
+
+    // TODO use CommentUpdate to pass data?
+    fn create_comment(origin, post_id: T::PostId, parent_id: Option<T::CommentId>, json: Vec<u8>, mentioned_accounts: Vec<T::AccountId>) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_call_group_is_not_paused(CallGroup::Posting)?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let mut post = Self::post_by_id(post_id).ok_or("Post was not found by id")?;
+      ensure!(!post.hidden, "Cannot comment on a hidden post");
+      Self::ensure_post_blog_is_active(post_id)?;
+      Self::ensure_post_embargo_allows(&owner, &post)?;
+      Self::ensure_comment_permission_allows(&owner, &post)?;
+
+      // An author of the post or of the parent comment gets notified about this comment:
+      let mut replied_account = Self::writer_by_post_id(post_id).unwrap_or_else(|| post.created.account.clone());
+      if let Some(id) = parent_id {
+        let parent = Self::comment_by_id(id).ok_or("Unknown parent comment id")?;
+        ensure!(parent.post_id == post_id, "Parent comment belongs to another post");
+        ensure!(!parent.hidden, "Cannot reply to a hidden comment");
+        replied_account = parent.created.account;
+      }
+
+      ensure!(json.len() <= Self::comment_max_len() as usize, "Comment JSON is too long");
+      Self::validate_mentions(&owner, &mentioned_accounts)?;
+      let mentioned_accounts = Self::filter_out_muting_accounts(mentioned_accounts, &json);
+      let notify_replied_account = replied_account != owner && !Self::is_muted(&replied_account, &json);
+
+      let blog = Self::blog_by_id(post.blog_id).ok_or("Blog was not found by id")?;
+
+      let comment_id = Self::next_comment_id();
+      let mut new_comment: Comment<T> = Comment {
+        id: comment_id,
+        parent_id,
+        post_id,
+        created: Self::new_change(owner.clone()),
+        updated: None,
+        json,
+        upvotes_count: 0,
+        downvotes_count: 0,
+        edits_count: 0,
+        replies_total_count: 0,
+        hidden: false,
+        is_post_author: false,
+        is_blog_owner: false,
+        is_blog_writer: false,
+      };
+      Self::set_comment_badges(&mut new_comment, &post, &blog);
+
+      <CommentById<T>>::insert(comment_id, new_comment);
+      <CommentIdsByPostId<T>>::mutate(post_id, |ids| ids.push(comment_id));
+      <CommentIdsByAccount<T>>::mutate(owner.clone(), |ids| ids.push(comment_id));
+      Self::increase_replies_total_count(parent_id);
+      Self::add_comment_to_page(&mut post, comment_id);
+      <NextCommentId<T>>::mutate(|n| { *n += T::CommentId::sa(1); });
+      Self::add_mentions(mentioned_accounts, Mention::Comment(comment_id));
+      Self::deposit_event(RawEvent::CommentCreated(owner.clone(), comment_id));
+      if let Some(persona) = Self::active_persona_by_account(owner.clone()) {
+        <PersonaByCommentId<T>>::insert(comment_id, persona);
+        Self::deposit_event(RawEvent::CommentCreatedAsPersona(owner.clone(), comment_id, persona));
+      }
+      if notify_replied_account {
+        Self::add_unread_reply(replied_account, comment_id, owner.clone());
+      }
+      Self::refund_sponsored_action(&owner, &blog);
+
+      Self::add_comment_to_blog_stats(post.blog_id, &owner);
+      post.comments_count += 1;
+      <PostById<T>>::insert(post_id, post); // TODO maybe use mutate instead of insert?
+    }
+
+    fn create_post_reaction(origin, post_id: T::PostId, kind: ReactionKind) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_call_group_is_not_paused(CallGroup::Reacting)?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      ensure!(
+        Self::post_reaction_id_by_account((owner.clone(), post_id)).is_none(),
+        "Account has already reacted to this post. To change a kind of reaction call update_post_reaction()"
+      );
+
+      let mut post = Self::post_by_id(post_id).ok_or("Post was not found by id")?;
+      ensure!(!post.hidden, "Cannot react to a hidden post");
+      Self::ensure_post_blog_is_active(post_id)?;
+      Self::ensure_post_embargo_allows(&owner, &post)?;
+      Self::ensure_post_reaction_kind_is_valid(post_id, kind)?;
+      ensure!(
+        Self::post_reactions_count(post_id) < Self::max_reactions_per_post(),
+        "Post has reached the max number of reactions"
+      );
+      let reaction_id = Self::new_reaction(owner.clone(), kind.clone());
+
+      Self::add_post_reaction_id(post_id, reaction_id);
+      <PostReactionIdByAccount<T>>::insert((owner.clone(), post_id), reaction_id);
+
+      Self::increase_post_reactions_count(&mut post, kind);
+      let blog_id = post.blog_id;
+      <BlogStatsByBlogId<T>>::mutate(blog_id, |stats| stats.post_reactions_count += 1);
+      // TODO maybe use mutate instead of insert?
+      <PostById<T>>::insert(post_id, post);
+
+      if !Self::aggregate_post_reaction_delta(post_id, Some(kind), None) {
+        Self::deposit_event(RawEvent::PostReactionCreated(owner.clone(), post_id, reaction_id));
+      }
+      if let Some(blog) = Self::blog_by_id(blog_id) {
+        Self::refund_sponsored_action(&owner, &blog);
+      }
+    }
+
+    fn create_comment_reaction(origin, comment_id: T::CommentId, kind: ReactionKind) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_call_group_is_not_paused(CallGroup::Reacting)?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      ensure!(
+        Self::comment_reaction_id_by_account((owner.clone(), comment_id)).is_none(),
+        "Account has already reacted to this comment. To change a kind of reaction call update_comment_reaction()"
+      );
+
+      let mut comment = Self::comment_by_id(comment_id).ok_or("Comment was not found by id")?;
+      ensure!(!comment.hidden, "Cannot react to a hidden comment");
+      Self::ensure_comment_blog_is_active(comment_id)?;
+      let post = Self::post_by_id(comment.post_id).ok_or("Post was not found by id")?;
+      Self::ensure_post_embargo_allows(&owner, &post)?;
+      Self::ensure_comment_reaction_kind_is_valid(comment_id, kind)?;
+      ensure!(
+        Self::reaction_ids_by_comment_id(comment_id).len() < Self::max_reactions_per_comment() as usize,
+        "Comment has reached the max number of reactions"
+      );
+      let reaction_id = Self::new_reaction(owner.clone(), kind.clone());
+
+      <ReactionIdsByCommentId<T>>::mutate(comment_id, |ids| ids.push(reaction_id));
+      <CommentReactionIdByAccount<T>>::insert((owner.clone(), comment_id), reaction_id);
+
+      Self::increase_comment_reactions_count(&mut comment, kind);
+      let post_id = comment.post_id;
+      // TODO maybe use mutate instead of insert?
+      <CommentById<T>>::insert(comment_id, comment);
+      Self::update_comments_votes_of_post(post_id, Some(kind), None);
+
+      Self::deposit_event(RawEvent::CommentReactionCreated(owner.clone(), comment_id, reaction_id));
+      if let Some(blog) = Self::post_by_id(post_id).and_then(|post| Self::blog_by_id(post.blog_id)) {
+        Self::refund_sponsored_action(&owner, &blog);
+      }
+    }
+
+    fn update_blog(origin, blog_id: T::BlogId, update: BlogUpdate<T>) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_call_group_is_not_paused(CallGroup::Posting)?;
+      Self::ensure_account_is_not_erased(&owner)?;
+      
+      let has_updates = 
+        update.writers.is_some() ||
+        update.slug.is_some() ||
+        update.json.is_some() ||
+        update.language.is_some();
+
+      ensure!(has_updates, "Nothing to update in a blog");
+
+      let mut blog = Self::blog_by_id(blog_id).ok_or("Blog was not found by id")?;
+      Self::ensure_blog_is_active(&blog)?;
+
+      // TODO ensure: blog writers also should be able to edit this blog:
+      ensure!(owner == blog.created.account, "Only a blog owner can update their blog");
+
+      let mut fields_updated = 0;
+      let mut removed_writers = Vec::new();
+
+      if let Some(writers) = update.writers {
+        if writers != blog.writers {
+          // Check the size first, so that a long list is not scanned below:
+          ensure!(writers.len() <= Self::max_blog_writers() as usize, "Too many blog writers");
+
+          // Writers can be removed here, but new writers should accept an invite first:
+          ensure!(
+            writers.iter().all(|w| blog.writers.contains(w)),
+            "New writers should be invited with invite_writer()"
+          );
+          let mut unique_writers = writers.clone();
+          unique_writers.sort();
+          unique_writers.dedup();
+          ensure!(unique_writers.len() == writers.len(), "Blog writers should not repeat");
+
+          removed_writers = blog.writers.iter().filter(|w| !writers.contains(w)).cloned().collect();
+          blog.writers = writers;
+          fields_updated += 1;
+        }
+      }
+
+      if let Some(slug) = update.slug {
+        if slug != blog.slug {
+          Self::ensure_blog_slug_is_valid(&slug)?;
+          Self::ensure_blog_slug_is_free(&slug, Some(blog_id))?;
+          Self::ensure_slug_change_is_not_throttled(blog_id)?;
+          Self::change_blog_slug(&mut blog, slug);
+          fields_updated += 1;
+        }
+      }
+
+      if let Some(json) = update.json {
+        if json != blog.json {
+          // TODO validate json.
+          blog.json = json;
+          fields_updated += 1;
+        }
+      }
+
+      if let Some(language) = update.language {
+        if language != blog.language {
+          Self::ensure_language_is_valid(&language)?;
+          blog.language = language;
+          fields_updated += 1;
+        }
+      }
+
+      // Update this blog only if at lest one field should be updated:
+      if fields_updated > 0 {
+        for writer in removed_writers {
+          <BlogIdsByWriter<T>>::mutate(writer, |ids| {
+            if let Some(index) = ids.iter().position(|x| *x == blog_id) {
+              ids.swap_remove(index);
+            }
+          });
+        }
+
+        blog.updated = Some(Self::new_change(owner.clone()));
+        <BlogById<T>>::insert(blog_id, blog);
+        Self::deposit_event(RawEvent::BlogUpdated(owner.clone(), blog_id));
+      }
+    }
+    
+    fn update_post(origin, post_id: T::PostId, update: PostUpdate<T>) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_call_group_is_not_paused(CallGroup::Posting)?;
+      Self::ensure_account_is_not_erased(&owner)?;
+      
+      let has_updates = 
+        update.blog_id.is_some() ||
+        update.slug.is_some() ||
+        update.json.is_some() ||
+        update.language.is_some() ||
+        update.license.is_some() ||
+        update.comment_permission.is_some() ||
+        update.links_to.is_some();
+
+      ensure!(has_updates, "Nothing to update in a post");
+
+      let mut post = Self::post_by_id(post_id).ok_or("Post was not found by id")?;
+
+      // TODO ensure: blog writers also should be able to edit this post:
+      ensure!(
+        Self::is_post_author(&owner, &post) || Self::can_edit_blog_posts(&owner, post.blog_id),
+        "Only a post owner or a blog editor can update this post"
+      );
+      Self::ensure_post_blog_is_active(post_id)?;
+
+      let old_blog_id = post.blog_id;
+      let old_language = post.language;
+      let mut fields_updated = 0;
+
+      let mut new_links = None;
+      if let Some(links_to) = update.links_to {
+        let links_to = Self::validate_links(Some(post_id), links_to)?;
+        if links_to != Self::links_by_post_id(post_id) {
+          new_links = Some(links_to);
+          fields_updated += 1;
+        }
+      }
+
+      if let Some(comment_permission) = update.comment_permission {
+        if comment_permission != post.comment_permission {
+          ensure!(
+            comment_permission.strictness() > post.comment_permission.strictness(),
+            "Comment permission of a post can only be changed to a stricter one"
+          );
+          post.comment_permission = comment_permission;
+          fields_updated += 1;
+        }
+      }
+
+      let mut new_license = None;
+      if let Some(license) = update.license {
+        if Some(&license) != post.license.as_ref() {
+          Self::ensure_license_is_valid(&Some(license.clone()))?;
+          if let Some(ref current) = post.license {
+            ensure!(
+              license.permissiveness() > current.permissiveness(),
+              "Post license can only be changed to a more permissive one"
+            );
+          }
+          post.license = Some(license.clone());
+          new_license = Some(license);
+          fields_updated += 1;
+        }
+      }
+
+      if let Some(language) = update.language {
+        if language != post.language {
+          Self::ensure_language_is_valid(&language)?;
+          post.language = language;
+          fields_updated += 1;
+        }
+      }
+
+      // The slug index is updated once every check below has passed:
+      let mut old_slug = None;
+      if let Some(slug) = update.slug {
+        if slug != post.slug {
+          // TODO validate slug.
+          ensure!(!<PostIdBySlug<T>>::exists(slug.clone()), "Post slug is not unique");
+          old_slug = Some(post.slug.clone());
+          post.slug = slug;
+          fields_updated += 1;
+        }
+      }
+
+      if let Some(json) = update.json {
+        if json != post.json {
+          // TODO validate json.
+          post.json = json;
+          fields_updated += 1;
+        }
+      }
+
+      // Move this post to another blog:
+      if let Some(blog_id) = update.blog_id {
+        if blog_id != post.blog_id {
+          // Editors can edit a post, but only its author or a blog owner can move it:
+          ensure!(
+            Self::is_post_author(&owner, &post) || Self::is_blog_owner(&owner, post.blog_id),
+            "Only a post author or a blog owner can move this post to another blog"
+          );
+          let new_blog = Self::blog_by_id(blog_id).ok_or("Blog was not found by id")?;
+          Self::ensure_blog_is_active(&new_blog)?;
+          ensure!(Self::can_create_post(&owner, &new_blog), "Account has no permission to create posts in this blog");
+          ensure!(
+            Self::can_create_post(&post.created.account, &new_blog),
+            "Post author has no permission to create posts in the new blog"
+          );
+
+          // A moved post is a new post for its new blog, so it goes through the same checks as create_post():
+          Self::ensure_blog_post_is_not_throttled(blog_id)?;
+          ensure!(
+            owner == new_blog.created.account || Self::posting_fee_by_blog_id(blog_id).is_zero(),
+            "Post cannot be moved to a blog with a posting fee"
+          );
+          if post.public_after.map_or(false, |public_after| public_after > <system::Module<T>>::block_number()) {
+            ensure!(
+              !Self::membership_tiers_by_blog_id(blog_id).is_empty(),
+              "Only a blog with membership tiers can embargo its posts"
+            );
+          }
+          let comment_ids = Self::comment_ids_by_post_id(post_id);
+          ensure!(
+            comment_ids.len() <= Self::max_comments_per_moved_post() as usize,
+            "Post has too many comments to be moved to another blog"
+          );
+
+          // Remove post_id from its old blog:
+          <PostIdsByBlogId<T>>::mutate(post.blog_id, |post_ids| {
+            if let Some(index) = post_ids.iter().position(|x| *x == post_id) {
+              post_ids.swap_remove(index);
+            }
+          });
+          
+          <RecentPostIdsByBlogId<T>>::mutate(post.blog_id, |ids| {
+            if let Some(index) = ids.iter().position(|x| *x == post_id) {
+              ids.remove(index);
+            }
+          });
+          Self::bump_blog_content_seq(post.blog_id);
+
+          // A series holds posts of its own blog only:
+          for series_id in Self::series_ids_by_blog_id(post.blog_id) {
+            let mut series_post_ids = Self::post_ids_by_series_id(series_id);
+            if let Some(index) = series_post_ids.iter().position(|x| *x == post_id) {
+              series_post_ids.remove(index);
+              if let Some(series) = Self::series_by_id(series_id) {
+                Self::update_series_posts(owner.clone(), series, series_post_ids);
+              }
+            }
+          }
+
+          <BlogById<T>>::mutate(post.blog_id, |blog_opt| {
+            if let Some(blog) = blog_opt.as_mut() {
+              blog.posts_count = blog.posts_count.saturating_sub(1);
+            }
+          });
+          let post_reactions_count = Self::post_reactions_count(post_id);
+          <BlogStatsByBlogId<T>>::mutate(post.blog_id, |stats| {
+            stats.posts_count = stats.posts_count.saturating_sub(1);
+            stats.comments_count = stats.comments_count.saturating_sub(post.comments_count);
+            stats.post_reactions_count = stats.post_reactions_count.saturating_sub(post_reactions_count);
+          });
+
+          // Add post_id to its new blog:
+          <PostIdsByBlogId<T>>::mutate(blog_id.clone(), |ids| ids.push(post_id));
+          Self::add_recent_post_id(blog_id, post_id);
+          <BlogById<T>>::mutate(blog_id, |blog_opt| {
+            if let Some(blog) = blog_opt.as_mut() {
+              blog.posts_count += 1;
+            }
+          });
+          <BlogStatsByBlogId<T>>::mutate(blog_id, |stats| {
+            stats.posts_count += 1;
+            stats.comments_count += post.comments_count;
+            stats.post_reactions_count += post_reactions_count;
+          });
+          <LastPostBlockByBlogId<T>>::insert(blog_id, <system::Module<T>>::block_number());
+
+          // Commenters of the post become commenters of its new blog. The old blog keeps them as well:
+          for comment_id in comment_ids {
+            if let Some(comment) = Self::comment_by_id(comment_id) {
+              Self::add_commenter_to_blog(blog_id, &comment.created.account);
+            }
+          }
+          post.blog_id = blog_id;
+          fields_updated += 1;
+        }
+      }
+
+      // Update this post only if at lest one field should be updated:
+      if fields_updated > 0 {
+        if let Some(old_slug) = old_slug {
+          <PostIdBySlug<T>>::remove(old_slug);
+          <PostIdBySlug<T>>::insert(post.slug.clone(), post_id);
+        }
+        if old_blog_id != post.blog_id || old_language != post.language {
+          if let Some(language) = old_language {
+            <PostIdsByBlogAndLanguage<T>>::mutate((old_blog_id, language), |ids| {
+              if let Some(index) = ids.iter().position(|x| *x == post_id) {
+                ids.swap_remove(index);
+              }
+            });
+          }
+          if let Some(language) = post.language {
+            <PostIdsByBlogAndLanguage<T>>::mutate((post.blog_id, language), |ids| ids.push(post_id));
+          }
+        }
+
+        if let Some(license) = new_license {
+          Self::add_license_to_history(post_id, license);
+        }
+        if let Some(links_to) = new_links {
+          Self::update_links(post_id, links_to);
+        }
+
+        let new_blog_id = post.blog_id;
+        post.updated = Some(Self::new_change(owner.clone()));
+        Self::bump_blog_content_seq(new_blog_id);
+        <PostById<T>>::insert(post_id, post);
+        Self::deposit_event(RawEvent::PostUpdated(owner.clone(), post_id));
+        if old_blog_id != new_blog_id {
+          Self::deposit_event(RawEvent::PostMoved(post_id, old_blog_id, new_blog_id));
+        }
+      }
+    }
+    
+    fn update_comment(origin, comment_id: T::CommentId, update: CommentUpdate) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_call_group_is_not_paused(CallGroup::Posting)?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let mut comment = Self::comment_by_id(comment_id).ok_or("Comment was not found by id")?;
+      ensure!(owner == comment.created.account, "Only comment author can update their comment");
+      Self::ensure_comment_blog_is_active(comment_id)?;
+
+      let json = update.json;
+      // TODO validate min length
+      ensure!(json.len() <= Self::comment_max_len() as usize, "Comment JSON is too long");
+      ensure!(json != comment.json, "New comment JSON is the same as old one");
+
+      if let Some(max_edits) = Self::max_comment_edits() {
+        ensure!(comment.edits_count < max_edits, "Comment has reached the max number of edits");
+      }
+      if let Some(edit_window) = Self::comment_edit_window() {
+        ensure!(
+          <system::Module<T>>::block_number() <= comment.created.block + edit_window,
+          "Comment edit window has expired"
+        );
+      }
+
+      comment.json = json;
+      comment.edits_count += 1;
+      comment.updated = Some(Self::new_change(owner.clone()));
+      <CommentById<T>>::insert(comment_id, comment);
+      Self::deposit_event(RawEvent::CommentUpdated(owner.clone(), comment_id));
+    }
+
+    fn set_muted_words(origin, words: Vec<Vec<u8>>) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      ensure!(words.len() <= Self::max_muted_words_per_account() as usize, "Too many muted words");
+      let word_max_len = Self::muted_word_max_len() as usize;
+      for word in words.iter() {
+        ensure!(!word.is_empty(), "Muted word cannot be empty");
+        ensure!(word.len() <= word_max_len, "Muted word is too long");
+      }
+
+      <MutedWordsByAccount<T>>::insert(owner.clone(), words);
+      Self::deposit_event(RawEvent::MutedWordsUpdated(owner));
+    }
+
+    // Personas cannot be removed, so that content keeps referring to the same persona id.
+    fn add_persona(origin, name: Vec<u8>) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let mut personas = Self::personas_by_account(owner.clone());
+      ensure!(
+        personas.len() < Self::max_personas_per_account() as usize,
+        "Account has reached the max number of personas"
+      );
+      Self::ensure_persona_name_is_valid(&personas, &name)?;
+
+      let persona = personas.len() as u16;
+      personas.push(name);
+      <PersonasByAccount<T>>::insert(owner.clone(), personas);
+      Self::deposit_event(RawEvent::PersonaAdded(owner, persona));
+    }
+
+    fn rename_persona(origin, persona: u16, name: Vec<u8>) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let mut personas = Self::personas_by_account(owner.clone());
+      ensure!((persona as usize) < personas.len(), "Persona was not found by id");
+      Self::ensure_persona_name_is_valid(&personas, &name)?;
+
+      personas[persona as usize] = name;
+      <PersonasByAccount<T>>::insert(owner.clone(), personas);
+      Self::deposit_event(RawEvent::PersonaRenamed(owner, persona));
+    }
+
+    // Choose a persona for new content, or None to publish as the account itself.
+    fn set_active_persona(origin, persona: Option<u16>) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      ensure!(persona != Self::active_persona_by_account(owner.clone()), "This persona is already active");
+      if let Some(persona) = persona {
+        ensure!((persona as usize) < Self::personas_by_account(owner.clone()).len(), "Persona was not found by id");
+        <ActivePersonaByAccount<T>>::insert(owner.clone(), persona);
+      } else {
+        <ActivePersonaByAccount<T>>::remove(owner.clone());
+      }
+      Self::deposit_event(RawEvent::ActivePersonaChanged(owner, persona));
+    }
+
+    // Mark all replies to the caller up to (including) the given index as read.
+    fn mark_replies_read(origin, up_to_index: u64) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      ensure!(up_to_index < Self::replies_count_by_account(owner.clone()), "There is no reply with this index");
+
+      let mut replies = Self::unread_replies_by_account(owner.clone());
+      let unread_count = replies.len();
+      replies.retain(|reply| reply.index > up_to_index);
+      ensure!(replies.len() < unread_count, "Replies up to this index are already read");
+
+      <UnreadRepliesByAccount<T>>::insert(owner.clone(), replies);
+      Self::deposit_event(RawEvent::RepliesRead(owner, up_to_index));
+    }
+
+    fn bookmark_post(origin, post_id: T::PostId) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      ensure!(<PostById<T>>::exists(post_id), "Post was not found by id");
+
+      let post_ids = Self::bookmarked_post_ids_by_account(owner.clone());
+      ensure!(!post_ids.contains(&post_id), "Post is already bookmarked");
+      ensure!(
+        post_ids.len() < Self::max_bookmarks_per_account() as usize,
+        "Account has reached the max number of bookmarks"
+      );
+
+      <BookmarkedPostIdsByAccount<T>>::mutate(owner.clone(), |ids| ids.push(post_id));
+      Self::deposit_event(RawEvent::PostBookmarked(owner.clone(), post_id));
+    }
+
+    fn unbookmark_post(origin, post_id: T::PostId) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let mut post_ids = Self::bookmarked_post_ids_by_account(owner.clone());
+      let index = post_ids.iter().position(|x| *x == post_id).ok_or("Post is not bookmarked")?;
+
+      // Keep the order of bookmarks, so they can be shown from the most recent one:
+      post_ids.remove(index);
+
+      <BookmarkedPostIdsByAccount<T>>::insert(owner.clone(), post_ids);
+      Self::deposit_event(RawEvent::PostUnbookmarked(owner.clone(), post_id));
+    }
+
+    fn hide_post(origin, post_id: T::PostId) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let mut post = Self::post_by_id(post_id).ok_or("Post was not found by id")?;
+      ensure!(
+        Self::is_post_author(&owner, &post) || Self::is_blog_owner(&owner, post.blog_id),
+        "Only a post owner or a blog owner can hide this post"
+      );
+      ensure!(!post.hidden, "Post is already hidden");
+
+      post.hidden = true;
+      Self::bump_blog_content_seq(post.blog_id);
+      <PostById<T>>::insert(post_id, post);
+      Self::deposit_event(RawEvent::PostHidden(owner.clone(), post_id));
+    }
+
+    fn unhide_post(origin, post_id: T::PostId) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let mut post = Self::post_by_id(post_id).ok_or("Post was not found by id")?;
+      ensure!(
+        Self::is_post_author(&owner, &post) || Self::is_blog_owner(&owner, post.blog_id),
+        "Only a post owner or a blog owner can unhide this post"
+      );
+      ensure!(post.hidden, "Post is not hidden");
+
+      post.hidden = false;
+      Self::bump_blog_content_seq(post.blog_id);
+      <PostById<T>>::insert(post_id, post);
+      Self::deposit_event(RawEvent::PostUnhidden(owner.clone(), post_id));
+    }
+
+    fn set_blog_nsfw(origin, blog_id: T::BlogId, nsfw: bool) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let mut blog = Self::blog_by_id(blog_id).ok_or("Blog was not found by id")?;
+      ensure!(owner == blog.created.account, "Only a blog owner can change the NSFW flag of their blog");
+      ensure!(blog.nsfw != nsfw, "Blog already has this NSFW flag");
+      ensure!(nsfw || !Self::nsfw_forced_by_blog_id(blog_id), "NSFW flag of this blog was set by moderation");
+
+      blog.nsfw = nsfw;
+      <BlogById<T>>::insert(blog_id, blog);
+      Self::deposit_event(RawEvent::BlogNsfwChanged(blog_id, nsfw));
+    }
+
+    fn set_post_nsfw(origin, post_id: T::PostId, nsfw: bool) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let mut post = Self::post_by_id(post_id).ok_or("Post was not found by id")?;
+      ensure!(
+        Self::is_post_author(&owner, &post) || Self::can_edit_blog_posts(&owner, post.blog_id),
+        "Only a post owner or a blog editor can change the NSFW flag of this post"
+      );
+      ensure!(post.nsfw != nsfw, "Post already has this NSFW flag");
+      ensure!(nsfw || !Self::nsfw_forced_by_post_id(post_id), "NSFW flag of this post was set by moderation");
+
+      post.nsfw = nsfw;
+      Self::bump_blog_content_seq(post.blog_id);
+      <PostById<T>>::insert(post_id, post);
+      Self::deposit_event(RawEvent::PostNsfwChanged(post_id, nsfw));
+    }
+
+    fn hide_comment(origin, comment_id: T::CommentId) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let mut comment = Self::comment_by_id(comment_id).ok_or("Comment was not found by id")?;
+      ensure!(!comment.hidden, "Comment is already hidden");
+
+      if owner != comment.created.account {
+        let post = Self::post_by_id(comment.post_id).ok_or("Post was not found by id")?;
+        ensure!(
+          Self::can_moderate_blog(&owner, post.blog_id),
+          "Only a comment author, a blog owner or a blog moderator can hide this comment"
+        );
+        <CommentHiddenByModerator<T>>::insert(comment_id, owner.clone());
+      }
+
+      comment.hidden = true;
+      <CommentById<T>>::insert(comment_id, comment);
+      Self::deposit_event(RawEvent::CommentHidden(owner.clone(), comment_id));
+    }
+
+    fn unhide_comment(origin, comment_id: T::CommentId) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let mut comment = Self::comment_by_id(comment_id).ok_or("Comment was not found by id")?;
+      ensure!(comment.hidden, "Comment is not hidden");
+
+      let post = Self::post_by_id(comment.post_id).ok_or("Post was not found by id")?;
+      let is_moderator = Self::can_moderate_blog(&owner, post.blog_id);
+      if !is_moderator {
+        ensure!(owner == comment.created.account, "Only a comment author, a blog owner or a blog moderator can unhide this comment");
+        ensure!(
+          !<CommentHiddenByModerator<T>>::exists(comment_id),
+          "Comment was hidden by a blog moderator and can be unhidden by a moderator only"
+        );
+      }
+
+      comment.hidden = false;
+      <CommentById<T>>::insert(comment_id, comment);
+      <CommentHiddenByModerator<T>>::remove(comment_id);
+      Self::deposit_event(RawEvent::CommentUnhidden(owner.clone(), comment_id));
+    }
+
+    // Pin a note to a post or a comment, e.g. to explain why it was hidden.
+    fn set_moderation_note(origin, entity: ModeratedEntity<T>, ipfs_hash: Vec<u8>, reason: u16) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let blog_id = Self::moderated_entity_blog_id(&entity)?;
+      ensure!(
+        Self::can_moderate_blog(&owner, blog_id),
+        "Only a blog owner or a blog moderator can pin a moderation note"
+      );
+      ensure!(!ipfs_hash.is_empty(), "Moderation note IPFS hash cannot be empty");
+      ensure!(ipfs_hash.len() <= Self::ipfs_hash_max_len() as usize, "Moderation note IPFS hash is too long");
+
+      if let Some(note) = Self::moderation_note_by_entity(&entity) {
+        ensure!(owner == note.created.account, "Only a moderator who pinned a moderation note can replace it");
+      }
+
+      <ModerationNoteByEntity<T>>::insert(entity.clone(), ModerationNote {
+        created: Self::new_change(owner.clone()),
+        ipfs_hash,
+        reason,
+      });
+      match entity {
+        ModeratedEntity::Post(post_id) => Self::deposit_event(RawEvent::PostModerationNoteSet(owner, post_id)),
+        ModeratedEntity::Comment(comment_id) => Self::deposit_event(RawEvent::CommentModerationNoteSet(owner, comment_id)),
+      }
+    }
+
+    fn remove_moderation_note(origin, entity: ModeratedEntity<T>) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let note = Self::moderation_note_by_entity(&entity).ok_or("There is no moderation note to remove")?;
+      ensure!(owner == note.created.account, "Only a moderator who pinned a moderation note can remove it");
+
+      Self::remove_moderation_note_of(owner, entity);
+    }
+
+    fn open_appeal(origin, comment_id: T::CommentId) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      let comment = Self::comment_by_id(comment_id).ok_or("Comment was not found by id")?;
+      ensure!(owner == comment.created.account, "Only a comment author can appeal against hiding of their comment");
+      ensure!(
+        comment.hidden && <CommentHiddenByModerator<T>>::exists(comment_id),
+        "Only a comment hidden by a blog moderator can be appealed"
+      );
+      // A resolved appeal is replaced, as a comment can be hidden again after it was restored:
+      ensure!(
+        Self::appeal_by_comment_id(comment_id).map_or(true, |appeal| appeal.status != AppealStatus::Open),
+        "Hiding of this comment has already been appealed"
+      );
+
+      let bond = Self::appeal_bond();
+      T::Currency::reserve(&owner, bond)?;
+
+      let appeal: Appeal<T> = Appeal {
+        created: Self::new_change(owner.clone()),
+        bond,
+        status: AppealStatus::Open,
+      };
+      <AppealByCommentId<T>>::insert(comment_id, appeal);
+      Self::deposit_event(RawEvent::AppealOpened(owner, comment_id, bond));
+    }
+
+    fn resolve_appeal(comment_id: T::CommentId, refund_bond: bool, restore_comment: bool) {
+      let mut appeal = Self::appeal_by_comment_id(comment_id).ok_or("Appeal was not found by comment id")?;
+      ensure!(appeal.status == AppealStatus::Open, "Appeal is already resolved");
+
+      let author = appeal.created.account.clone();
+      if refund_bond {
+        T::Currency::unreserve(&author, appeal.bond);
+        appeal.status = AppealStatus::Refunded;
+      } else {
+        let _ = T::Currency::slash_reserved(&author, appeal.bond);
+        appeal.status = AppealStatus::Slashed;
+      }
+      <AppealByCommentId<T>>::insert(comment_id, appeal);
+
+      if restore_comment {
+        <CommentById<T>>::mutate(comment_id, |comment_opt| {
+          if let Some(comment) = comment_opt.as_mut() {
+            comment.hidden = false;
+          }
+        });
+        <CommentHiddenByModerator<T>>::remove(comment_id);
+      }
+
+      Self::deposit_event(RawEvent::AppealResolved(comment_id, refund_bond, restore_comment));
+    }
+
+    fn record_post_views(origin, views: Vec<(T::PostId, u32)>) {
+      let oracle = ensure_signed(origin)?;
+      Self::ensure_module_is_not_paused()?;
+      Self::ensure_account_is_not_erased(&oracle)?;
+
+      if let Some(views_oracle) = Self::views_oracle() {
+        ensure!(oracle == views_oracle, "Only the views oracle can record post views");
+      } else {
+        return Err("No views oracle defined");
+      }
+
+      ensure!(!views.is_empty(), "No post views to record");
+      ensure!(views.len() <= Self::max_views_batch_len() as usize, "Too many post views in one batch");
+
+      // Check all posts before the first write, so a batch is recorded either fully or not at all:
+      for (post_id, _) in views.iter() {
+        ensure!(<PostById<T>>::exists(post_id), "Post was not found by id");
+      }
+
+      for (post_id, views_count) in views.iter() {
+        <PostById<T>>::mutate(post_id, |post_opt| {
+          if let Some(ref mut post) = post_opt {
+            post.views_count = post.views_count.saturating_add(*views_count as u64);
+          }
+        });
+      }
+
+      Self::deposit_event(RawEvent::PostViewsRecorded(oracle, views.len() as u32));
+    }
+
+    fn set_views_oracle(oracle: T::AccountId) {
+      <ViewsOracle<T>>::put(oracle);
+    }
+
+    fn set_comment_edit_limits(max_edits: Option<u16>, edit_window: Option<T::BlockNumber>) {
+      match max_edits {
+        Some(max_edits) => <MaxCommentEdits<T>>::put(max_edits),
+        None => <MaxCommentEdits<T>>::kill(),
+      }
+      match edit_window {
+        Some(edit_window) => <CommentEditWindow<T>>::put(edit_window),
+        None => <CommentEditWindow<T>>::kill(),
+      }
+    }
+
+    // Recompute counters of an entity from storage indices and report (and fix if asked) a mismatch.
+    fn audit_entity(entity: AuditedEntity<T>, heal: bool) {
+      match entity {
+        AuditedEntity::Blog(blog_id) => {
+          let mut blog = Self::blog_by_id(blog_id).ok_or("Blog was not found by id")?;
+          let posts_count = Self::post_ids_by_blog_id(blog_id).len() as u32;
+
+          if blog.posts_count != posts_count {
+            if heal {
+              blog.posts_count = posts_count;
+              <BlogById<T>>::insert(blog_id, blog);
+            }
+            Self::deposit_event(RawEvent::BlogCountersMismatch(blog_id, heal));
+          }
+        },
+        AuditedEntity::Post(post_id) => {
+          let mut post = Self::post_by_id(post_id).ok_or("Post was not found by id")?;
+          let comments = Self::comment_ids_by_post_id(post_id)
+            .into_iter()
+            .filter_map(|id| Self::comment_by_id(id))
+            .collect::<Vec<_>>();
+          let reaction_ids = Self::post_reaction_ids(post_id, 0, Self::post_reactions_count(post_id));
+          let (upvotes_count, downvotes_count) = Self::count_votes(reaction_ids);
+
+          let mut audited = post.clone();
+          audited.comments_count = comments.len() as u32;
+          audited.upvotes_count = upvotes_count;
+          audited.downvotes_count = downvotes_count;
+          audited.comments_upvotes_count = comments.iter().map(|c| c.upvotes_count).sum();
+          audited.comments_downvotes_count = comments.iter().map(|c| c.downvotes_count).sum();
+
+          if audited != post {
+            if heal {
+              post = audited;
+              <PostById<T>>::insert(post_id, post);
+            }
+            Self::deposit_event(RawEvent::PostCountersMismatch(post_id, heal));
+          }
+        },
+        AuditedEntity::Comment(comment_id) => {
+          let mut comment = Self::comment_by_id(comment_id).ok_or("Comment was not found by id")?;
+          let (upvotes_count, downvotes_count) = Self::count_votes(Self::reaction_ids_by_comment_id(comment_id));
+
+          if comment.upvotes_count != upvotes_count || comment.downvotes_count != downvotes_count {
+            if heal {
+              comment.upvotes_count = upvotes_count;
+              comment.downvotes_count = downvotes_count;
+              <CommentById<T>>::insert(comment_id, comment);
+            }
+            Self::deposit_event(RawEvent::CommentCountersMismatch(comment_id, heal));
+          }
+        },
+      }
+    }
+
+    fn set_follower_tiers(tiers: Vec<FollowerTier<T>>) {
+      ensure!(
+        tiers.windows(2).all(|pair| pair[0].min_followers < pair[1].min_followers),
+        "Follower tiers should be sorted by min followers"
+      );
+      <FollowerTiers<T>>::put(tiers);
+    }
+
+    fn set_min_blocks_between_posts(min_blocks: T::BlockNumber) {
+      <MinBlocksBetweenPosts<T>>::put(min_blocks);
+    }
+
+    fn set_post_throttle_exempt(blog_id: T::BlogId, exempt: bool) {
+      if exempt {
+        <PostThrottleExemptBlogs<T>>::insert(blog_id, true);
+      } else {
+        <PostThrottleExemptBlogs<T>>::remove(blog_id);
+      }
+    }
+
+    // Blogs that already have more writers keep them, but cannot get new ones.
+    fn set_max_blog_writers(max_writers: u32) {
+      <MaxBlogWriters<T>>::put(max_writers);
+    }
+
+    fn set_slug_change_cooldown(cooldown: T::BlockNumber) {
+      <SlugChangeCooldown<T>>::put(cooldown);
+    }
+
+    // Change a blog slug regardless of the cooldown, e.g. to resolve a dispute over a slug.
+    fn force_change_blog_slug(blog_id: T::BlogId, slug: Vec<u8>) {
+      let mut blog = Self::blog_by_id(blog_id).ok_or("Blog was not found by id")?;
+      ensure!(slug != blog.slug, "Blog already has this slug");
+      Self::ensure_blog_slug_is_valid(&slug)?;
+      Self::ensure_blog_slug_is_free(&slug, Some(blog_id))?;
+
+      Self::change_blog_slug(&mut blog, slug);
+      <BlogById<T>>::insert(blog_id, blog);
+    }
+
+    fn force_remove_moderation_note(entity: ModeratedEntity<T>) {
+      let note = Self::moderation_note_by_entity(&entity).ok_or("There is no moderation note to remove")?;
+      Self::remove_moderation_note_of(note.created.account, entity);
+    }
+
+    // Clearing a flag here also lets the owner manage it again.
+    fn force_set_blog_nsfw(blog_id: T::BlogId, nsfw: bool) {
+      let mut blog = Self::blog_by_id(blog_id).ok_or("Blog was not found by id")?;
+      <NsfwForcedByBlogId<T>>::insert(blog_id, nsfw);
+      if blog.nsfw != nsfw {
+        blog.nsfw = nsfw;
+        <BlogById<T>>::insert(blog_id, blog);
+        Self::deposit_event(RawEvent::BlogNsfwChanged(blog_id, nsfw));
+      }
+    }
+
+    fn force_set_post_nsfw(post_id: T::PostId, nsfw: bool) {
+      let mut post = Self::post_by_id(post_id).ok_or("Post was not found by id")?;
+      <NsfwForcedByPostId<T>>::insert(post_id, nsfw);
+      if post.nsfw != nsfw {
+        post.nsfw = nsfw;
+        Self::bump_blog_content_seq(post.blog_id);
+        <PostById<T>>::insert(post_id, post);
+        Self::deposit_event(RawEvent::PostNsfwChanged(post_id, nsfw));
+      }
+    }
+
+    fn set_tombstone_account(account: T::AccountId) {
+      <TombstoneAccount<T>>::put(account);
+    }
+
+    fn set_paused(paused: bool) {
+      <Paused<T>>::put(paused);
+    }
+
+    fn set_call_group_paused(group: CallGroup, paused: bool) {
+      <PausedCallGroups<T>>::mutate(|groups| {
+        let index = groups.iter().position(|g| *g == group);
+        match (paused, index) {
+          (true, None) => groups.push(group),
+          (false, Some(index)) => { groups.swap_remove(index); },
+          _ => {},
+        }
+      });
+    }
+
+    fn set_sponsored_post_fee(fee: BalanceOf<T>) {
+      <SponsoredPostFee<T>>::put(fee);
+    }
+
+    fn set_sponsored_actions(refund: BalanceOf<T>, max_per_account: u32) {
+      <SponsoredActionRefund<T>>::put(refund);
+      <MaxSponsoredActionsPerAccount<T>>::put(max_per_account);
+    }
+
+    fn set_appeal_bond(bond: BalanceOf<T>) {
+      <AppealBond<T>>::put(bond);
+    }
+
+    fn set_posting_fee_hold_period(period: T::BlockNumber) {
+      <PostingFeeHoldPeriod<T>>::put(period);
+    }
+
+    fn set_submission_settings(deposit: BalanceOf<T>, period: T::BlockNumber, max_pending_per_blog: u32) {
+      <SubmissionDeposit<T>>::put(deposit);
+      <SubmissionPeriod<T>>::put(period);
+      <MaxPendingSubmissionsPerBlog<T>>::put(max_pending_per_blog);
+    }
+
+    fn set_aggregate_reaction_events(enabled: bool) {
+      <AggregateReactionEvents<T>>::put(enabled);
+    }
+
+    fn set_max_reactions(per_post: u32, per_comment: u32) {
+      <MaxReactionsPerPost<T>>::put(per_post);
+      <MaxReactionsPerComment<T>>::put(per_comment);
+    }
+
+    fn update_post_reaction(origin, post_id: T::PostId, reaction_id: T::ReactionId, new_kind: ReactionKind) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_call_group_is_not_paused(CallGroup::Reacting)?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      match Self::post_reaction_id_by_account((owner.clone(), post_id)) {
+        Some(id) => ensure!(id == reaction_id, "Account has reacted to this post with another reaction"),
+        None => fail!("Account has not reacted to this post yet. Use create_post_reaction()"),
+      }
+      Self::ensure_post_blog_is_active(post_id)?;
+      Self::ensure_post_reaction_kind_is_valid(post_id, new_kind)?;
+
+      let mut reaction = Self::reaction_by_id(reaction_id).ok_or("Reaction was not found by id")?;
+      ensure!(owner == reaction.created.account, "Only reaction owner can update their reaction");
+      ensure!(reaction.kind != new_kind, "Current account reaction is the same as requested");
+
+      let old_kind = reaction.kind;
+      reaction.kind = new_kind;
+      reaction.updated = Some(Self::new_change(owner.clone()));
+      <ReactionById<T>>::insert(reaction_id, reaction);
+
+      let mut post = Self::post_by_id(post_id).ok_or("Post was not found by id")?;
+      Self::increase_post_reactions_count(&mut post, new_kind);
+      Self::decrease_post_reactions_count(&mut post, old_kind);
+      // TODO maybe use mutate instead of insert?
+      <PostById<T>>::insert(post_id, post);
+
+      if !Self::aggregate_post_reaction_delta(post_id, Some(new_kind), Some(old_kind)) {
+        Self::deposit_event(RawEvent::PostReactionUpdated(owner.clone(), post_id, reaction_id));
+      }
+    }
+
+    fn update_comment_reaction(origin, comment_id: T::CommentId, reaction_id: T::ReactionId, new_kind: ReactionKind) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_call_group_is_not_paused(CallGroup::Reacting)?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      match Self::comment_reaction_id_by_account((owner.clone(), comment_id)) {
+        Some(id) => ensure!(id == reaction_id, "Account has reacted to this comment with another reaction"),
+        None => fail!("Account has not reacted to this comment yet. Use create_comment_reaction()"),
+      }
+      Self::ensure_comment_blog_is_active(comment_id)?;
+      Self::ensure_comment_reaction_kind_is_valid(comment_id, new_kind)?;
+
+      let mut reaction = Self::reaction_by_id(reaction_id).ok_or("Reaction was not found by id")?;
+      ensure!(owner == reaction.created.account, "Only reaction owner can update their reaction");
+      ensure!(reaction.kind != new_kind, "Current account reaction is the same as requested");
+
+      let old_kind = reaction.kind;
+      reaction.kind = new_kind;
+      reaction.updated = Some(Self::new_change(owner.clone()));
+      <ReactionById<T>>::insert(reaction_id, reaction);
+
+      let mut comment = Self::comment_by_id(comment_id).ok_or("Comment was not found by id")?;
+      Self::increase_comment_reactions_count(&mut comment, new_kind);
+      Self::decrease_comment_reactions_count(&mut comment, old_kind);
+      let post_id = comment.post_id;
+      // TODO maybe use mutate instead of insert?
+      <CommentById<T>>::insert(comment_id, comment);
+      Self::update_comments_votes_of_post(post_id, Some(new_kind), Some(old_kind));
+
+      Self::deposit_event(RawEvent::CommentReactionUpdated(owner.clone(), comment_id, reaction_id));
+    }
+
+    // TODO fn delete_blog(origin, blog_id: T::BlogId) {
+      // TODO only owner can delete
+      // TODO unfollow all blog followers
+    // }
+    
+    // TODO fn delete_post(origin, post_id: T::PostId) {}
+    
+    // TODO fn delete_comment(origin, comment_id: T::CommentId) {}
+
+    fn delete_post_reaction(origin, post_id: T::PostId, reaction_id: T::ReactionId) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_call_group_is_not_paused(CallGroup::Reacting)?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      match Self::post_reaction_id_by_account((owner.clone(), post_id)) {
+        Some(id) => ensure!(id == reaction_id, "Account has reacted to this post with another reaction"),
+        None => fail!("There is no post reaction by account that could be deleted"),
+      }
+      Self::ensure_post_blog_is_active(post_id)?;
+      
+      let reaction = Self::reaction_by_id(reaction_id).ok_or("Reaction was not found by id")?;
+      ensure!(owner == reaction.created.account, "Only reaction owner can delete their reaction");
+      ensure!(
+        <ReactionIndexByPostId<T>>::exists((post_id, reaction_id)),
+        "Reaction was not found on this post"
+      );
+
+      Self::remove_post_reaction_id(post_id, reaction_id);
+
+      let mut post = Self::post_by_id(post_id).ok_or("Post was not found by id")?;
+      Self::decrease_post_reactions_count(&mut post, reaction.kind);
+      <BlogStatsByBlogId<T>>::mutate(post.blog_id, |stats| {
+        stats.post_reactions_count = stats.post_reactions_count.saturating_sub(1);
+      });
+      // TODO maybe use mutate instead of insert?
+      <PostById<T>>::insert(post_id, post);
+
+      <ReactionById<T>>::remove(reaction_id);
+      Self::remove_reaction_id_of_account(&owner, reaction_id);
+      <PostReactionIdByAccount<T>>::remove((owner.clone(), post_id));
+
+      if !Self::aggregate_post_reaction_delta(post_id, None, Some(reaction.kind)) {
+        Self::deposit_event(RawEvent::PostReactionDeleted(owner.clone(), post_id, reaction_id));
+      }
+    }
+
+    fn delete_comment_reaction(origin, comment_id: T::CommentId, reaction_id: T::ReactionId) {
+      let owner = ensure_signed(origin)?;
+      Self::ensure_call_group_is_not_paused(CallGroup::Reacting)?;
+      Self::ensure_account_is_not_erased(&owner)?;
+
+      match Self::comment_reaction_id_by_account((owner.clone(), comment_id)) {
+        Some(id) => ensure!(id == reaction_id, "Account has reacted to this comment with another reaction"),
+        None => fail!("There is no comment reaction by account that could be deleted"),
+      }
+      Self::ensure_comment_blog_is_active(comment_id)?;
+      
+      let reaction = Self::reaction_by_id(reaction_id).ok_or("Reaction was not found by id")?;
+      ensure!(owner == reaction.created.account, "Only reaction owner can delete their reaction");
+
+      <ReactionIdsByCommentId<T>>::mutate(comment_id, |ids| {
+        if let Some(index) = ids.iter().position(|x| *x == reaction_id) {
+          ids.swap_remove(index);
+        }
+      });
+      
+      let mut comment = Self::comment_by_id(comment_id).ok_or("Comment was not found by id")?;
+      Self::decrease_comment_reactions_count(&mut comment, reaction.kind);
+      let post_id = comment.post_id;
+      // TODO maybe use mutate instead of insert?
+      <CommentById<T>>::insert(comment_id, comment);
+      Self::update_comments_votes_of_post(post_id, None, Some(reaction.kind));
+
+      <ReactionById<T>>::remove(reaction_id);
+      Self::remove_reaction_id_of_account(&owner, reaction_id);
+      <CommentReactionIdByAccount<T>>::remove((owner.clone(), comment_id));
+
+      Self::deposit_event(RawEvent::CommentReactionDeleted(owner.clone(), comment_id, reaction_id));
+    }
+
+    // TODO spend some tokens on: create/update a blog/post/comment.
+  }
+}
+
+// Layouts of a blog, a post and a comment as they were stored
+// before their counters have been widened from u16 to u32.
+
+#[derive(Encode, Decode)]
+struct LegacyBlog<T: Trait> {
+  id: T::BlogId,
+  created: Change<T>,
+  updated: Option<Change<T>>,
+  writers: Vec<T::AccountId>,
+  slug: Vec<u8>,
+  json: Vec<u8>,
+  posts_count: u16,
+}
+
+#[derive(Encode, Decode)]
+struct LegacyPost<T: Trait> {
+  id: T::PostId,
+  blog_id: T::BlogId,
+  created: Change<T>,
+  updated: Option<Change<T>>,
+  slug: Vec<u8>,
+  json: Vec<u8>,
+  comments_count: u16,
+  upvotes_count: u16,
+  downvotes_count: u16,
+}
+
+#[derive(Encode, Decode)]
+struct LegacyComment<T: Trait> {
+  id: T::CommentId,
+  parent_id: Option<T::CommentId>,
+  post_id: T::PostId,
+  created: Change<T>,
+  updated: Option<Change<T>>,
+  json: Vec<u8>,
+  upvotes_count: u16,
+  downvotes_count: u16,
+}
+
+impl<T: Trait> From<LegacyBlog<T>> for Blog<T> {
+  fn from(old: LegacyBlog<T>) -> Self {
+    Blog {
+      id: old.id,
+      created: old.created,
+      updated: old.updated,
+      writers: old.writers,
+      slug: old.slug,
+      json: old.json,
+      posts_count: old.posts_count as u32,
+      verified: None,
+      channel: None,
+      status: BlogStatus::Active,
+      language: None,
+      nsfw: false,
+    }
+  }
+}
+
+impl<T: Trait> From<LegacyPost<T>> for Post<T> {
+  fn from(old: LegacyPost<T>) -> Self {
+    Post {
+      id: old.id,
+      blog_id: old.blog_id,
+      created: old.created,
+      updated: old.updated,
+      slug: old.slug,
+      json: old.json,
+      comments_count: old.comments_count as u32,
+      upvotes_count: old.upvotes_count as u32,
+      downvotes_count: old.downvotes_count as u32,
+      comment_pages_count: 0,
+      comments_upvotes_count: 0,
+      comments_downvotes_count: 0,
+      views_count: 0,
+      hidden: false,
+      is_sponsored: false,
+      language: None,
+      license: None,
+      public_after: None,
+      comment_permission: CommentPermission::Everyone,
+      nsfw: false,
+    }
+  }
+}
+
+impl<T: Trait> From<LegacyComment<T>> for Comment<T> {
+  fn from(old: LegacyComment<T>) -> Self {
+    Comment {
+      id: old.id,
+      parent_id: old.parent_id,
+      post_id: old.post_id,
+      created: old.created,
+      updated: old.updated,
+      json: old.json,
+      upvotes_count: old.upvotes_count as u32,
+      downvotes_count: old.downvotes_count as u32,
+      edits_count: 0,
+      replies_total_count: 0,
+      hidden: false,
+      // Set by the migration from the post and the blog of a comment:
+      is_post_author: false,
+      is_blog_owner: false,
+      is_blog_writer: false,
+    }
+  }
+}
+
+decl_runtime_apis! {
+  // Read-only queries over the social graph, so front-ends can use a single state_call
+  // instead of many raw storage reads.
+  pub trait BlogsApi<AccountId, BlogId, PostSummary> where
+    AccountId: Codec,
+    BlogId: Codec,
+    PostSummary: Codec,
+  {
+    fn blog_ids_followed_by(account: AccountId) -> Vec<BlogId>;
+
+    fn followers_of_blog(blog_id: BlogId, offset: u64, limit: u64) -> Vec<AccountId>;
+
+    fn posts_by_blog(blog_id: BlogId, offset: u64, limit: u64) -> Vec<PostSummary>;
+
+    // Recent posts of all blogs followed by an account, newest first:
+    fn get_feed(account: AccountId, offset: u64, limit: u64) -> Vec<PostSummary>;
+  }
+}
+
+impl<T: Trait> Module<T> {
+
+  pub fn blog_ids_followed_by(account: T::AccountId) -> Vec<T::BlogId> {
+    Self::blogs_followed_by_account(account)
+  }
+
+  pub fn followers_of_blog(blog_id: T::BlogId, offset: u64, limit: u64) -> Vec<T::AccountId> {
+    Self::paginate(Self::blog_followers(blog_id), offset, limit)
+  }
+
+  // Hidden posts and posts under an embargo are left out.
+  pub fn posts_by_blog(blog_id: T::BlogId, offset: u64, limit: u64) -> Vec<PostSummary<T>> {
+    let now = <system::Module<T>>::block_number();
+    Self::post_ids_by_blog_id(blog_id)
+      .into_iter()
+      .filter_map(|post_id| Self::post_by_id(post_id))
+      .filter(|post| !post.hidden && post.public_after.map_or(true, |public_after| now >= public_after))
+      .skip(offset as usize)
+      .take(rstd::cmp::min(limit, MAX_POSTS_PAGE_LEN) as usize)
+      .map(PostSummary::from)
+      .collect()
+  }
+
+  // Hidden posts are left out, as well as embargoed posts that the account cannot read yet.
+  pub fn get_feed(account: T::AccountId, offset: u64, limit: u64) -> Vec<PostSummary<T>> {
+    let mut blog_ids = Self::blogs_followed_by_account(account.clone());
+    for topic in Self::topics_followed_by_account(account.clone()) {
+      blog_ids.extend(Self::blog_ids_by_topic(topic));
+    }
+    blog_ids.sort();
+    blog_ids.dedup();
+
+    // Recent posts of every blog are sorted by id, and post ids grow over time.
+    // So the newest posts are taken from the ends of these lists one by one, until the page is full:
+    let mut recent_ids: Vec<Vec<T::PostId>> = blog_ids.into_iter()
+      .map(|blog_id| Self::recent_post_ids_by_blog_id(blog_id))
+      .collect();
+    let mut heads = BTreeSet::new();
+    for (index, ids) in recent_ids.iter_mut().enumerate() {
+      if let Some(post_id) = ids.pop() {
+        heads.insert((post_id, index));
+      }
+    }
+
+    let limit = rstd::cmp::min(limit, MAX_POSTS_PAGE_LEN) as usize;
+    let mut to_skip = offset;
+    let mut feed = Vec::new();
+    let mut last_post_id = None;
+
+    while feed.len() < limit {
+      let (post_id, index) = match heads.iter().next_back() {
+        Some(head) => *head,
+        None => break,
+      };
+      heads.remove(&(post_id, index));
+      if let Some(next_id) = recent_ids[index].pop() {
+        heads.insert((next_id, index));
+      }
+
+      if last_post_id == Some(post_id) {
+        continue;
+      }
+      last_post_id = Some(post_id);
+
+      let post = match Self::post_by_id(post_id) {
+        Some(post) => post,
+        None => continue,
+      };
+      if post.hidden || Self::ensure_post_embargo_allows(&account, &post).is_err() {
+        continue;
+      }
+      if to_skip > 0 {
+        to_skip -= 1;
+      } else {
+        feed.push(PostSummary::from(post));
+      }
+    }
+    feed
+  }
+
+  // Fills recent posts of every blog from all of its posts.
+  // This should ONLY be called from the migration module on a runtime upgrade.
+  pub fn migrate_recent_post_ids() {
+    let mut blog_id = T::BlogId::sa(1);
+    while blog_id < Self::next_blog_id() {
+      let mut post_ids = Self::post_ids_by_blog_id(blog_id);
+      post_ids.sort();
+      for post_id in post_ids {
+        Self::add_recent_post_id(blog_id, post_id);
+      }
+      blog_id += T::BlogId::sa(1);
+    }
+  }
+
+  // Splits comments of every post into pages.
+  // This should ONLY be called from the migration module on a runtime upgrade.
+  pub fn migrate_comment_pages() {
+    let mut post_id = T::PostId::sa(1);
+    while post_id < Self::next_post_id() {
+      if let Some(mut post) = Self::post_by_id(post_id) {
+        for comment_id in Self::comment_ids_by_post_id(post_id) {
+          Self::add_comment_to_page(&mut post, comment_id);
+        }
+        <PostById<T>>::insert(post_id, post);
+      }
+      post_id += T::PostId::sa(1);
+    }
+  }
+
+  // Re-encodes all blogs, posts and comments stored with the legacy layout.
+  // This should ONLY be called from the migration module on a runtime upgrade.
+  pub fn migrate_to_u32_counters() {
+    let mut blog_id = T::BlogId::sa(1);
+    while blog_id < Self::next_blog_id() {
+      if let Some(old) = storage::get::<LegacyBlog<T>>(&<BlogById<T>>::key_for(blog_id)) {
+        <BlogById<T>>::insert(blog_id, Blog::from(old));
+      }
+      blog_id += T::BlogId::sa(1);
+    }
+
+    let mut post_id = T::PostId::sa(1);
+    while post_id < Self::next_post_id() {
+      if let Some(old) = storage::get::<LegacyPost<T>>(&<PostById<T>>::key_for(post_id)) {
+        <PostById<T>>::insert(post_id, Post::from(old));
+      }
+      post_id += T::PostId::sa(1);
+    }
+
+    let mut comment_id = T::CommentId::sa(1);
+    while comment_id < Self::next_comment_id() {
+      if let Some(old) = storage::get::<LegacyComment<T>>(&<CommentById<T>>::key_for(comment_id)) {
+        let mut comment = Comment::from(old);
+        // Posts and blogs have been migrated above:
+        if let Some(post) = Self::post_by_id(comment.post_id) {
+          if let Some(blog) = Self::blog_by_id(post.blog_id) {
+            Self::set_comment_badges(&mut comment, &post, &blog);
+          }
+        }
+        <CommentById<T>>::insert(comment_id, comment);
+      }
+      comment_id += T::CommentId::sa(1);
+    }
+
+    // Roll up reactions of the migrated comments to their posts:
+    let mut comment_id = T::CommentId::sa(1);
+    while comment_id < Self::next_comment_id() {
+      if let Some(comment) = Self::comment_by_id(comment_id) {
+        <PostById<T>>::mutate(comment.post_id, |post_opt| {
+          if let Some(post) = post_opt.as_mut() {
+            post.comments_upvotes_count += comment.upvotes_count;
+            post.comments_downvotes_count += comment.downvotes_count;
+          }
+        });
+        Self::increase_replies_total_count(comment.parent_id);
+      }
+      comment_id += T::CommentId::sa(1);
+    }
+  }
+
+  // Fills the reverse index of blog writers from all existing blogs.
+  // This should ONLY be called from the migration module on a runtime upgrade.
+  pub fn migrate_blog_ids_by_writer() {
+    let mut blog_id = T::BlogId::sa(1);
+    while blog_id < Self::next_blog_id() {
+      if let Some(blog) = Self::blog_by_id(blog_id) {
+        for writer in blog.writers {
+          <BlogIdsByWriter<T>>::mutate(writer, |ids| ids.push(blog_id));
+        }
+      }
+      blog_id += T::BlogId::sa(1);
+    }
+  }
+
+  // Fills the indexes of posts, comments and reactions by their accounts from all existing content.
+  // This should ONLY be called from the migration module on a runtime upgrade.
+  pub fn migrate_content_ids_by_account() {
+    let mut post_id = T::PostId::sa(1);
+    while post_id < Self::next_post_id() {
+      if let Some(post) = Self::post_by_id(post_id) {
+        <PostIdsByAccount<T>>::mutate(post.created.account, |ids| ids.push(post_id));
+      }
+      post_id += T::PostId::sa(1);
+    }
+
+    let mut comment_id = T::CommentId::sa(1);
+    while comment_id < Self::next_comment_id() {
+      if let Some(comment) = Self::comment_by_id(comment_id) {
+        <CommentIdsByAccount<T>>::mutate(comment.created.account, |ids| ids.push(comment_id));
+      }
+      comment_id += T::CommentId::sa(1);
+    }
+
+    let mut reaction_id = T::ReactionId::sa(1);
+    while reaction_id < Self::next_reaction_id() {
+      if let Some(reaction) = Self::reaction_by_id(reaction_id) {
+        <ReactionIdsByAccount<T>>::mutate(reaction.created.account, |ids| ids.push(reaction_id));
+      }
+      reaction_id += T::ReactionId::sa(1);
+    }
+  }
+
+  // Counts blog stats from all existing followers, posts, comments and post reactions.
+  // This should ONLY be called from the migration module on a runtime upgrade.
+  pub fn migrate_blog_stats() {
+    let mut blog_id = T::BlogId::sa(1);
+    while blog_id < Self::next_blog_id() {
+      let followers_count = Self::blog_followers(blog_id).len() as u32;
+      <BlogStatsByBlogId<T>>::mutate(blog_id, |stats| stats.followers_count = followers_count);
+      blog_id += T::BlogId::sa(1);
+    }
+
+    let mut post_id = T::PostId::sa(1);
+    while post_id < Self::next_post_id() {
+      if let Some(post) = Self::post_by_id(post_id) {
+        let post_reactions_count = Self::post_reactions_count(post_id);
+        <BlogStatsByBlogId<T>>::mutate(post.blog_id, |stats| {
+          stats.posts_count += 1;
+          stats.post_reactions_count += post_reactions_count;
+        });
+        for comment_id in Self::comment_ids_by_post_id(post_id) {
+          if let Some(comment) = Self::comment_by_id(comment_id) {
+            Self::add_comment_to_blog_stats(post.blog_id, &comment.created.account);
+          }
+        }
+      }
+      post_id += T::PostId::sa(1);
+    }
+  }
+
+  // Moves reaction ids of every post from a single vector to separate entries.
+  // This should ONLY be called from the migration module on a runtime upgrade.
+  pub fn migrate_post_reaction_ids() {
+    let mut post_id = T::PostId::sa(1);
+    while post_id < Self::next_post_id() {
+      for reaction_id in <ReactionIdsByPostId<T>>::take(post_id) {
+        Self::add_post_reaction_id(post_id, reaction_id);
+      }
+      post_id += T::PostId::sa(1);
+    }
+  }
+
+  pub fn post_reaction_ids(post_id: T::PostId, offset: u32, limit: u32) -> Vec<T::ReactionId> {
+    let end = rstd::cmp::min(offset.saturating_add(limit), Self::post_reactions_count(post_id));
+    (offset..end)
+      .filter_map(|index| Self::reaction_id_by_post_id_and_index((post_id, index)))
+      .collect()
+  }
+
+  fn add_comment_to_page(post: &mut Post<T>, comment_id: T::CommentId) {
+    let last_page = post.comment_pages_count.checked_sub(1);
+    let page = match last_page {
+      Some(page) if (Self::comment_ids_by_post_id_and_page((post.id, page)).len() as u32) < COMMENTS_PAGE_SIZE => page,
+      _ => {
+        post.comment_pages_count += 1;
+        post.comment_pages_count - 1
+      }
+    };
+    <CommentIdsByPostIdAndPage<T>>::mutate((post.id, page), |ids| ids.push(comment_id));
+  }
+
+  fn add_recent_post_id(blog_id: T::BlogId, post_id: T::PostId) {
+    let max_recent_posts = Self::max_recent_posts_per_blog() as usize;
+
+    <RecentPostIdsByBlogId<T>>::mutate(blog_id, |ids| {
+      ids.push(post_id);
+      // Keep ids sorted, as a moved post can be older than the latest posts of its new blog:
+      ids.sort();
+      if ids.len() > max_recent_posts {
+        let overflow = ids.len() - max_recent_posts;
+        ids.drain(..overflow);
+      }
+    });
+  }
+
+  fn add_post_reaction_id(post_id: T::PostId, reaction_id: T::ReactionId) {
+    let index = Self::post_reactions_count(post_id);
+    <ReactionIdByPostIdAndIndex<T>>::insert((post_id, index), reaction_id);
+    <ReactionIndexByPostId<T>>::insert((post_id, reaction_id), index);
+    <PostReactionsCount<T>>::insert(post_id, index + 1);
+  }
+
+  fn remove_post_reaction_id(post_id: T::PostId, reaction_id: T::ReactionId) {
+    if let Some(index) = <ReactionIndexByPostId<T>>::take((post_id, reaction_id)) {
+      let last_index = Self::post_reactions_count(post_id).saturating_sub(1);
+
+      // Move the last reaction to the place of the removed one:
+      if index != last_index {
+        if let Some(last_id) = Self::reaction_id_by_post_id_and_index((post_id, last_index)) {
+          <ReactionIdByPostIdAndIndex<T>>::insert((post_id, index), last_id);
+          <ReactionIndexByPostId<T>>::insert((post_id, last_id), index);
+        }
+      }
+
+      <ReactionIdByPostIdAndIndex<T>>::remove((post_id, last_index));
+      <PostReactionsCount<T>>::insert(post_id, last_index);
+    }
+  }
+
+  fn paginate<I>(items: Vec<I>, offset: u64, limit: u64) -> Vec<I> {
+    items.into_iter().skip(offset as usize).take(limit as usize).collect()
+  }
+
+  // Whether an account follows at least one blog of another account.
+  pub fn follows_blog_of(follower: &T::AccountId, owner: &T::AccountId) -> bool {
+    Self::blog_ids_by_owner(owner)
+      .into_iter()
+      .any(|blog_id| Self::blog_followed_by_account((follower.clone(), blog_id)))
+  }
+
+  pub fn has_active_membership(account: &T::AccountId, blog_id: T::BlogId) -> bool {
+    let now = <system::Module<T>>::block_number();
+    Self::membership_ids_by_account(account)
+      .into_iter()
+      .filter_map(|id| Self::membership_by_id(id))
+      .any(|m| m.blog_id == blog_id && now < m.expires_at)
+  }
+
+  fn ensure_comment_permission_allows(account: &T::AccountId, post: &Post<T>) -> dispatch::Result {
+    match post.comment_permission {
+      CommentPermission::Everyone => Ok(()),
+      CommentPermission::BlogFollowers => {
+        ensure!(
+          <BlogFollowedByAccount<T>>::exists((account.clone(), post.blog_id)),
+          "Only followers of the blog can comment on this post"
+        );
+        Ok(())
+      },
+      CommentPermission::Nobody => Err("Comments are disabled on this post"),
+    }
+  }
+
+  // Blog members, as well as those who can post to a blog, can interact with an embargoed post.
+  fn ensure_post_embargo_allows(account: &T::AccountId, post: &Post<T>) -> dispatch::Result {
+    if let Some(public_after) = post.public_after {
+      if <system::Module<T>>::block_number() < public_after {
+        let blog = Self::blog_by_id(post.blog_id).ok_or("Blog was not found by id")?;
+        ensure!(
+          Self::can_create_post(account, &blog) || Self::has_active_membership(account, blog.id),
+          "Post is available to blog members only until its embargo ends"
+        );
+      }
+    }
+    Ok(())
+  }
+
+  // Returns sorted links without duplicates.
+  fn validate_links(post_id: Option<T::PostId>, links_to: Vec<T::PostId>) -> Result<Vec<T::PostId>, &'static str> {
+    let mut links = links_to;
+    links.sort();
+    links.dedup();
+
+    ensure!(links.len() <= Self::max_links_per_post() as usize, "Post has too many links to other posts");
+    for link in links.iter() {
+      ensure!(Some(*link) != post_id, "Post cannot link to itself");
+      ensure!(<PostById<T>>::exists(link), "Linked post was not found by id");
+    }
+    Ok(links)
+  }
+
+  // Replaces links of a post and updates backlinks of the posts that were added or removed.
+  fn update_links(post_id: T::PostId, links_to: Vec<T::PostId>) {
+    let old_links = Self::links_by_post_id(post_id);
+
+    for link in old_links.iter().filter(|x| !links_to.contains(x)) {
+      <BacklinksByPostId<T>>::mutate(link, |ids| {
+        if let Some(index) = ids.iter().position(|x| *x == post_id) {
+          ids.swap_remove(index);
+        }
+      });
+    }
+    for link in links_to.iter().filter(|x| !old_links.contains(x)) {
+      <BacklinksByPostId<T>>::mutate(link, |ids| ids.push(post_id));
+    }
+
+    if links_to.is_empty() {
+      <LinksByPostId<T>>::remove(post_id);
+    } else {
+      <LinksByPostId<T>>::insert(post_id, links_to);
+    }
+  }
+
+  fn ensure_persona_name_is_valid(personas: &[Vec<u8>], name: &Vec<u8>) -> dispatch::Result {
+    ensure!(!name.is_empty(), "Persona name cannot be empty");
+    ensure!(name.len() <= Self::persona_name_max_len() as usize, "Persona name is too long");
+    ensure!(!personas.contains(name), "Account already has a persona with this name");
+    Ok(())
+  }
+
+  fn ensure_license_is_valid(license: &Option<ContentLicense>) -> dispatch::Result {
+    if let Some(ContentLicense::Custom(ipfs_hash)) = license {
+      ensure!(!ipfs_hash.is_empty(), "Custom license IPFS hash cannot be empty");
+      ensure!(ipfs_hash.len() <= Self::ipfs_hash_max_len() as usize, "Custom license IPFS hash is too long");
+    }
+    Ok(())
+  }
+
+  fn add_license_to_history(post_id: T::PostId, license: ContentLicense) {
+    let now = <system::Module<T>>::block_number();
+    <LicenseHistoryByPostId<T>>::mutate(post_id, |history| history.push((license, now)));
+  }
+
+  fn ensure_language_is_valid(language: &Option<[u8; 2]>) -> dispatch::Result {
+    if let Some(code) = language {
+      ensure!(code.iter().all(|c| c.is_ascii_lowercase()), "Language should be a lowercase ISO 639-1 code");
+    }
+    Ok(())
+  }
+
+  // The highest follower tier reached by a blog, if any.
+  pub fn follower_tier(blog_id: T::BlogId) -> Option<FollowerTier<T>> {
+    let followers_count = Self::blog_stats_by_blog_id(blog_id).followers_count;
+    Self::follower_tiers()
+      .into_iter()
+      .take_while(|tier| tier.min_followers <= followers_count)
+      .last()
+  }
+
+  // Handles consist of lowercase latin letters, digits and underscores.
+  fn ensure_handle_is_valid(handle: &[u8]) -> dispatch::Result {
+    ensure!(handle.len() >= Self::handle_min_len() as usize, "Handle is too short");
+    ensure!(handle.len() <= Self::handle_max_len() as usize, "Handle is too long");
+    ensure!(
+      handle.iter().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || *c == b'_'),
+      "Handle can contain only lowercase latin letters, digits and underscores"
+    );
+    Ok(())
+  }
+
+  fn ensure_blog_post_is_not_throttled(blog_id: T::BlogId) -> dispatch::Result {
+    if Self::post_throttle_exempt_blogs(blog_id) {
+      return Ok(());
+    }
+    if let Some(last_post_block) = Self::last_post_block_by_blog_id(blog_id) {
+      let min_blocks = Self::follower_tier(blog_id)
+        .map_or(Self::min_blocks_between_posts(), |tier| tier.min_blocks_between_posts);
+      ensure!(
+        <system::Module<T>>::block_number() >= last_post_block + min_blocks,
+        "Too many posts in this blog recently. Try again in a few blocks"
+      );
+    }
+    Ok(())
+  }
+
+  fn ensure_account_is_not_erased(account: &T::AccountId) -> dispatch::Result {
+    ensure!(!Self::erased_accounts(account), "Account has been erased");
+    Ok(())
+  }
+
+  fn ensure_module_is_not_paused() -> dispatch::Result {
+    ensure!(!Self::paused(), "Blogs module is paused");
+    Ok(())
+  }
+
+  fn ensure_call_group_is_not_paused(group: CallGroup) -> dispatch::Result {
+    Self::ensure_module_is_not_paused()?;
+    ensure!(!Self::paused_call_groups().contains(&group), "This kind of calls is paused in blogs module");
+    Ok(())
+  }
+
+  fn ensure_blog_exists(blog_id: T::BlogId) -> dispatch::Result {
+    ensure!(<BlogById<T>>::exists(blog_id), "Unknown blog id");
+    Ok(())
+  }
+
+  fn is_blog_owner(account: &T::AccountId, blog_id: T::BlogId) -> bool {
+    Self::blog_by_id(blog_id).map_or(false, |blog| blog.created.account == *account)
+  }
+
+  fn has_any_blog_role(account: &T::AccountId, blog_id: T::BlogId, roles: &[BlogRole]) -> bool {
+    Self::role_by_blog_and_account((blog_id, account.clone())).map_or(false, |role| roles.contains(&role))
+  }
+
+  fn can_create_post(account: &T::AccountId, blog: &Blog<T>) -> bool {
+    blog.created.account == *account ||
+    blog.writers.contains(account) ||
+    Self::has_any_blog_role(account, blog.id, &[BlogRole::Author, BlogRole::Editor])
+  }
+
+  fn can_edit_blog_posts(account: &T::AccountId, blog_id: T::BlogId) -> bool {
+    Self::is_blog_owner(account, blog_id) ||
+    Self::has_any_blog_role(account, blog_id, &[BlogRole::Editor])
+  }
+
+  fn ensure_can_manage_series(account: &T::AccountId, series: &PostSeries<T>) -> dispatch::Result {
+    ensure!(
+      *account == series.created.account || Self::can_edit_blog_posts(account, series.blog_id),
+      "Only a series owner or a blog editor can manage this series"
+    );
+    let blog = Self::blog_by_id(series.blog_id).ok_or("Blog was not found by id")?;
+    Self::ensure_blog_is_active(&blog)
+  }
+
+  fn update_series_posts(owner: T::AccountId, mut series: PostSeries<T>, post_ids: Vec<T::PostId>) {
+    let series_id = series.id;
+    series.updated = Some(Self::new_change(owner.clone()));
+    <SeriesById<T>>::insert(series_id, series);
+    <PostIdsBySeriesId<T>>::insert(series_id, post_ids);
+    Self::deposit_event(RawEvent::SeriesUpdated(owner, series_id));
+  }
+
+  // An account that publishes posts on behalf of a blog. Nobody holds its private key.
+  pub fn blog_account(blog_id: T::BlogId) -> T::AccountId {
+    let hash = T::Hashing::hash_of(&(b"blog", blog_id));
+    T::AccountId::decode(&mut hash.as_ref()).unwrap_or_default()
+  }
+
+  pub fn org_account(org_id: u64) -> T::AccountId {
+    let hash = T::Hashing::hash_of(&(b"org", org_id));
+    T::AccountId::decode(&mut hash.as_ref()).unwrap_or_default()
+  }
+
+  fn is_post_author(account: &T::AccountId, post: &Post<T>) -> bool {
+    *account == post.created.account ||
+    Self::writer_by_post_id(post.id).map_or(false, |writer| writer == *account)
+  }
+
+  // Badges show whether a comment author is the post author, the blog owner or a blog writer.
+  fn set_comment_badges(comment: &mut Comment<T>, post: &Post<T>, blog: &Blog<T>) {
+    let author = comment.created.account.clone();
+    comment.is_post_author = Self::is_post_author(&author, post);
+    comment.is_blog_owner = author == blog.created.account;
+    comment.is_blog_writer = blog.writers.contains(&author) ||
+      Self::has_any_blog_role(&author, blog.id, &[BlogRole::Author, BlogRole::Editor]);
+  }
+
+  fn moderated_entity_blog_id(entity: &ModeratedEntity<T>) -> Result<T::BlogId, &'static str> {
+    let post_id = match entity {
+      ModeratedEntity::Post(post_id) => *post_id,
+      ModeratedEntity::Comment(comment_id) => Self::comment_by_id(comment_id).ok_or("Comment was not found by id")?.post_id,
+    };
+    let post = Self::post_by_id(post_id).ok_or("Post was not found by id")?;
+    Ok(post.blog_id)
+  }
+
+  // Events of a removed note refer to the moderator who pinned it.
+  fn remove_moderation_note_of(moderator: T::AccountId, entity: ModeratedEntity<T>) {
+    <ModerationNoteByEntity<T>>::remove(&entity);
+    match entity {
+      ModeratedEntity::Post(post_id) => Self::deposit_event(RawEvent::PostModerationNoteRemoved(moderator, post_id)),
+      ModeratedEntity::Comment(comment_id) => Self::deposit_event(RawEvent::CommentModerationNoteRemoved(moderator, comment_id)),
+    }
+  }
+
+  fn can_moderate_blog(account: &T::AccountId, blog_id: T::BlogId) -> bool {
+    Self::is_blog_owner(account, blog_id) ||
+    Self::has_any_blog_role(account, blog_id, &[BlogRole::Moderator])
+  }
+
+  // Resolves a current blog slug as well as an old one that still redirects to its blog.
+  pub fn blog_id_by_slug_or_redirect(slug: Vec<u8>) -> Option<T::BlogId> {
+    Self::blog_id_by_slug(slug.clone()).or_else(|| {
+      Self::slug_redirect(slug)
+        .filter(|(_, expires_at)| *expires_at > <system::Module<T>>::block_number())
+        .map(|(blog_id, _)| blog_id)
+    })
+  }
+
+  fn ensure_blog_slug_is_valid(slug: &Vec<u8>) -> dispatch::Result {
+    ensure!(slug.len() >= Self::slug_min_len() as usize, "Blog slug is too short");
+    ensure!(slug.len() <= Self::slug_max_len() as usize, "Blog slug is too long");
+    Ok(())
+  }
+
+  // An expired redirect does not block the slug: it is removed lazily once the slug is taken.
+  fn ensure_blog_slug_is_free(slug: &Vec<u8>, blog_id: Option<T::BlogId>) -> dispatch::Result {
+    ensure!(!<BlogIdBySlug<T>>::exists(slug.clone()), "Blog slug is not unique");
+
+    if let Some((redirect_blog_id, expires_at)) = Self::slug_redirect(slug.clone()) {
+      let is_expired = expires_at <= <system::Module<T>>::block_number();
+      ensure!(
+        is_expired || Some(redirect_blog_id) == blog_id,
+        "Blog slug is reserved by a redirect to another blog"
+      );
+    }
+
+    Ok(())
+  }
+
+  fn ensure_slug_change_is_not_throttled(blog_id: T::BlogId) -> dispatch::Result {
+    if let Some(last_change) = Self::last_slug_change_by_blog_id(blog_id) {
+      ensure!(
+        <system::Module<T>>::block_number() >= last_change + Self::slug_change_cooldown(),
+        "Blog slug was changed recently. Wait until the cooldown ends"
+      );
+    }
+    Ok(())
+  }
+
+  fn change_blog_slug(blog: &mut Blog<T>, new_slug: Vec<u8>) {
+    let now = <system::Module<T>>::block_number();
+    let expires_at = now + Self::slug_redirect_period();
+    let old_slug = blog.slug.clone();
+
+    <BlogIdBySlug<T>>::remove(blog.slug.clone());
+    <SlugRedirects<T>>::insert(blog.slug.clone(), (blog.id, expires_at));
+
+    <SlugRedirects<T>>::remove(new_slug.clone());
+    <BlogIdBySlug<T>>::insert(new_slug.clone(), blog.id);
+    <LastSlugChangeByBlogId<T>>::insert(blog.id, now);
+    blog.slug = new_slug.clone();
+    Self::deposit_event(RawEvent::BlogSlugChanged(blog.id, old_slug, new_slug));
+  }
 
-      Self::deposit_event(RawEvent::CommentReactionUpdated(owner.clone(), comment_id, reaction_id));
+  fn ensure_verification_authority(account: &T::AccountId) -> dispatch::Result {
+    if let Some(authority) = Self::verification_authority() {
+      ensure!(*account == authority, "Only the verification authority can verify blogs");
+      Ok(())
+    } else {
+      Err("No verification authority defined")
     }
+  }
 
-    // TODO fn delete_blog(origin, blog_id: T::BlogId) {
-      // TODO only owner can delete
-      // TODO unfollow all blog followers
-    // }
-    
-    // TODO fn delete_post(origin, post_id: T::PostId) {}
-    
-    // TODO fn delete_comment(origin, comment_id: T::CommentId) {}
+  fn ensure_blog_is_active(blog: &Blog<T>) -> dispatch::Result {
+    ensure!(blog.status == BlogStatus::Active, "Blog is archived");
+    Ok(())
+  }
 
-    fn delete_post_reaction(origin, post_id: T::PostId, reaction_id: T::ReactionId) {
-      let owner = ensure_signed(origin)?;
+  fn ensure_post_blog_is_active(post_id: T::PostId) -> dispatch::Result {
+    let post = Self::post_by_id(post_id).ok_or("Post was not found by id")?;
+    let blog = Self::blog_by_id(post.blog_id).ok_or("Blog was not found by id")?;
+    Self::ensure_blog_is_active(&blog)
+  }
 
-      ensure!(
-        <PostReactionIdByAccount<T>>::exists((owner.clone(), post_id)),
-        "There is no post reaction by account that could be deleted"
-      );
-      
-      let reaction = Self::reaction_by_id(reaction_id).ok_or("Reaction was not found by id")?;
-      ensure!(owner == reaction.created.account, "Only reaction owner can delete their reaction");
+  fn ensure_comment_blog_is_active(comment_id: T::CommentId) -> dispatch::Result {
+    let comment = Self::comment_by_id(comment_id).ok_or("Comment was not found by id")?;
+    Self::ensure_post_blog_is_active(comment.post_id)
+  }
 
-      <ReactionIdsByPostId<T>>::mutate(post_id, |ids| {
-        if let Some(index) = ids.iter().position(|x| *x == reaction_id) {
-          ids.swap_remove(index);
-        }
+  // Creates content straight in storage, skipping validation, fees and events.
+  // Seed accounts author blogs, follow them, comment and react in turn.
+  // Used by the genesis config. Benchmarks can call it directly, as it is not exposed as a call.
+  pub fn seed_content(accounts: &[T::AccountId], seed: ContentSeed) {
+    if accounts.is_empty() {
+      return;
+    }
+    let account_at = |i: u32| accounts[i as usize % accounts.len()].clone();
+
+    for b in 0..seed.blogs {
+      let owner = account_at(b);
+      let blog_id = Self::next_blog_id();
+      let slug = Self::seed_slug(b"seed-blog-", <T::BlogId as As<u64>>::as_(blog_id));
+
+      <BlogById<T>>::insert(blog_id, Blog {
+        id: blog_id,
+        created: Self::new_change(owner.clone()),
+        updated: None,
+        writers: vec![],
+        slug: slug.clone(),
+        json: vec![],
+        posts_count: seed.posts_per_blog,
+        verified: None,
+        channel: None,
+        status: BlogStatus::Active,
+        language: None,
+        nsfw: false,
       });
+      <BlogIdsByOwner<T>>::mutate(owner.clone(), |ids| ids.push(blog_id));
+      <BlogIdBySlug<T>>::insert(slug, blog_id);
+      <NextBlogId<T>>::mutate(|n| { *n += T::BlogId::sa(1); });
 
-      let mut post = Self::post_by_id(post_id).ok_or("Post was not found by id")?;
-      match reaction.kind {
-        ReactionKind::Upvote => post.upvotes_count -= 1,
-        ReactionKind::Downvote => post.downvotes_count -= 1,
+      // The first follower is the blog owner, as in create_blog():
+      for f in 0..=seed.followers_per_blog {
+        let follower = account_at(b + f);
+        if !<BlogFollowedByAccount<T>>::exists((follower.clone(), blog_id)) {
+          <BlogsFollowedByAccount<T>>::mutate(follower.clone(), |ids| ids.push(blog_id));
+          <BlogFollowers<T>>::mutate(blog_id, |ids| ids.push(follower.clone()));
+          <BlogStatsByBlogId<T>>::mutate(blog_id, |stats| stats.followers_count += 1);
+          <BlogFollowedByAccount<T>>::insert((follower, blog_id), true);
+        }
       }
-      // TODO maybe use mutate instead of insert?
-      <PostById<T>>::insert(post_id, post);
 
-      <ReactionById<T>>::remove(reaction_id);
-      <PostReactionIdByAccount<T>>::remove((owner.clone(), post_id));
+      for p in 0..seed.posts_per_blog {
+        let post_id = Self::next_post_id();
+        let slug = Self::seed_slug(b"seed-post-", <T::PostId as As<u64>>::as_(post_id));
+        let mut post = Self::new_post(post_id, blog_id, owner.clone(), slug.clone(), vec![]);
+
+        for c in 0..seed.comments_per_post {
+          let comment_id = Self::next_comment_id();
+          let commenter = account_at(b + p + c);
+          // Seeded posts are authored by their blog owners:
+          let is_owner = commenter == owner;
+          <CommentById<T>>::insert(comment_id, Comment {
+            id: comment_id,
+            parent_id: None,
+            post_id,
+            created: Self::new_change(commenter.clone()),
+            updated: None,
+            json: vec![],
+            upvotes_count: 0,
+            downvotes_count: 0,
+            edits_count: 0,
+            replies_total_count: 0,
+            hidden: false,
+            is_post_author: is_owner,
+            is_blog_owner: is_owner,
+            is_blog_writer: false,
+          });
+          <CommentIdsByPostId<T>>::mutate(post_id, |ids| ids.push(comment_id));
+          <CommentIdsByAccount<T>>::mutate(commenter.clone(), |ids| ids.push(comment_id));
+          Self::add_comment_to_page(&mut post, comment_id);
+          <NextCommentId<T>>::mutate(|n| { *n += T::CommentId::sa(1); });
+          Self::add_comment_to_blog_stats(blog_id, &commenter);
+          post.comments_count += 1;
+        }
 
-      Self::deposit_event(RawEvent::PostReactionDeleted(owner.clone(), post_id, reaction_id));
-    }
+        for r in 0..seed.reactions_per_post {
+          let reactor = account_at(b + p + r);
+          if Self::post_reaction_id_by_account((reactor.clone(), post_id)).is_some() {
+            continue;
+          }
+          let kind = if r % 2 == 0 { ReactionKind::Upvote } else { ReactionKind::Downvote };
+          let reaction_id = Self::new_reaction(reactor.clone(), kind);
+          Self::add_post_reaction_id(post_id, reaction_id);
+          <PostReactionIdByAccount<T>>::insert((reactor, post_id), reaction_id);
+          Self::increase_post_reactions_count(&mut post, kind);
+          <BlogStatsByBlogId<T>>::mutate(blog_id, |stats| stats.post_reactions_count += 1);
+        }
 
-    fn delete_comment_reaction(origin, comment_id: T::CommentId, reaction_id: T::ReactionId) {
-      let owner = ensure_signed(origin)?;
+        <BlogStatsByBlogId<T>>::mutate(blog_id, |stats| stats.posts_count += 1);
+        <PostById<T>>::insert(post_id, post);
+        <PostIdsByBlogId<T>>::mutate(blog_id, |ids| ids.push(post_id));
+        Self::add_recent_post_id(blog_id, post_id);
+        <PostIdBySlug<T>>::insert(slug, post_id);
+        <PostIdsByAccount<T>>::mutate(owner.clone(), |ids| ids.push(post_id));
+        <NextPostId<T>>::mutate(|n| { *n += T::PostId::sa(1); });
+      }
+    }
+  }
 
-      ensure!(
-        <CommentReactionIdByAccount<T>>::exists((owner.clone(), comment_id)),
-        "There is no comment reaction by account that could be deleted"
-      );
-      
-      let reaction = Self::reaction_by_id(reaction_id).ok_or("Reaction was not found by id")?;
-      ensure!(owner == reaction.created.account, "Only reaction owner can delete their reaction");
+  fn remove_submission(submission: &PostSubmission<T>) {
+    <SubmissionById<T>>::remove(submission.id);
+    <PendingSubmissionIdsByBlogId<T>>::mutate(submission.blog_id, |ids| {
+      if let Some(index) = ids.iter().position(|x| *x == submission.id) {
+        ids.remove(index);
+      }
+    });
+    <PendingSubmissionIdsByAccount<T>>::mutate(submission.created.account.clone(), |ids| {
+      if let Some(index) = ids.iter().position(|x| *x == submission.id) {
+        ids.swap_remove(index);
+      }
+    });
+  }
 
-      <ReactionIdsByCommentId<T>>::mutate(comment_id, |ids| {
-        if let Some(index) = ids.iter().position(|x| *x == reaction_id) {
-          ids.swap_remove(index);
-        }
-      });
-      
-      let mut comment = Self::comment_by_id(comment_id).ok_or("Comment was not found by id")?;
-      match reaction.kind {
-        ReactionKind::Upvote => comment.upvotes_count -= 1,
-        ReactionKind::Downvote => comment.downvotes_count -= 1,
+  fn remove_writer_invite(blog_id: T::BlogId, account: &T::AccountId) {
+    <WriterInviteByBlogAndAccount<T>>::remove((blog_id, account.clone()));
+    <InvitedBlogIdsByAccount<T>>::mutate(account.clone(), |ids| {
+      if let Some(index) = ids.iter().position(|x| *x == blog_id) {
+        ids.swap_remove(index);
       }
-      // TODO maybe use mutate instead of insert?
-      <CommentById<T>>::insert(comment_id, comment);
+    });
+  }
 
-      <ReactionById<T>>::remove(reaction_id);
-      <CommentReactionIdByAccount<T>>::remove((owner.clone(), comment_id));
+  fn remove_org_proposal(proposal: &OrgProposal<T>) {
+    <OrgProposalById<T>>::remove(proposal.id);
+    <PendingProposalIdsByOrgId<T>>::mutate(proposal.org_id, |ids| {
+      if let Some(index) = ids.iter().position(|x| *x == proposal.id) {
+        ids.remove(index);
+      }
+    });
+  }
 
-      Self::deposit_event(RawEvent::CommentReactionDeleted(owner.clone(), comment_id, reaction_id));
+  // Stores a new post and adds it to the indexes and counters of its blog.
+  // Both create_post() and approve_submission() publish posts this way. Validation is up to a caller.
+  // A writer of a post on behalf of a blog should be stored before.
+  fn insert_new_post(mut blog: Blog<T>, post: Post<T>) {
+    let blog_id = blog.id;
+    let post_id = post.id;
+
+    <PostIdsByBlogId<T>>::mutate(blog_id, |ids| ids.push(post_id));
+    <LastPostBlockByBlogId<T>>::insert(blog_id, <system::Module<T>>::block_number());
+    Self::add_recent_post_id(blog_id, post_id);
+    if let Some(language) = post.language {
+      <PostIdsByBlogAndLanguage<T>>::mutate((blog_id, language), |ids| ids.push(post_id));
     }
+    <PostIdBySlug<T>>::insert(post.slug.clone(), post_id);
+    let account = Self::writer_by_post_id(post_id).unwrap_or_else(|| post.created.account.clone());
+    <PostIdsByAccount<T>>::mutate(account, |ids| ids.push(post_id));
+    <PostById<T>>::insert(post_id, post);
+    <NextPostId<T>>::mutate(|n| { *n += T::PostId::sa(1); });
+    Self::bump_blog_content_seq(blog_id);
+
+    <BlogStatsByBlogId<T>>::mutate(blog_id, |stats| stats.posts_count += 1);
+    blog.posts_count += 1;
+    <BlogById<T>>::insert(blog_id, blog);
+  }
 
-    // TODO spend some tokens on: create/update a blog/post/comment.
+  // A post with no reactions, comments or optional settings.
+  fn new_post(post_id: T::PostId, blog_id: T::BlogId, author: T::AccountId, slug: Vec<u8>, json: Vec<u8>) -> Post<T> {
+    Post {
+      id: post_id,
+      blog_id,
+      created: Self::new_change(author),
+      updated: None,
+      slug,
+      json,
+      comments_count: 0,
+      upvotes_count: 0,
+      downvotes_count: 0,
+      comment_pages_count: 0,
+      comments_upvotes_count: 0,
+      comments_downvotes_count: 0,
+      views_count: 0,
+      hidden: false,
+      is_sponsored: false,
+      language: None,
+      license: None,
+      public_after: None,
+      comment_permission: CommentPermission::Everyone,
+      nsfw: false,
+    }
   }
-}
 
-impl<T: Trait> Module<T> {
+  // Builds a unique slug like "seed-blog-42".
+  fn seed_slug(prefix: &[u8], id: u64) -> Vec<u8> {
+    let mut digits = Vec::new();
+    let mut n = id;
+    loop {
+      digits.push(b'0' + (n % 10) as u8);
+      n /= 10;
+      if n == 0 {
+        break;
+      }
+    }
+    digits.reverse();
 
-  fn ensure_blog_exists(blog_id: T::BlogId) -> dispatch::Result {
-    ensure!(<BlogById<T>>::exists(blog_id), "Unknown blog id");
-    Ok(())
+    let mut slug = prefix.to_vec();
+    slug.extend(digits);
+    slug
   }
 
   fn new_change(account: T::AccountId) -> Change<T> {
@@ -687,21 +4528,518 @@ impl<T: Trait> Module<T> {
     let reaction_id = Self::next_reaction_id();
     let new_reaction: Reaction<T> = Reaction {
       id: reaction_id,
-      created: Self::new_change(account),
+      created: Self::new_change(account.clone()),
       updated: None,
       kind
     };
 
     <ReactionById<T>>::insert(reaction_id, new_reaction);
+    <ReactionIdsByAccount<T>>::mutate(account, |ids| ids.push(reaction_id));
     <NextReactionId<T>>::mutate(|n| { *n += T::ReactionId::sa(1); });
 
     reaction_id
   }
 
+  fn remove_reaction_id_of_account(account: &T::AccountId, reaction_id: T::ReactionId) {
+    <ReactionIdsByAccount<T>>::mutate(account.clone(), |ids| {
+      if let Some(index) = ids.iter().position(|x| *x == reaction_id) {
+        ids.swap_remove(index);
+      }
+    });
+  }
+
+  fn bump_blog_content_seq(blog_id: T::BlogId) {
+    <BlogContentSeq<T>>::mutate(blog_id, |seq| *seq = seq.wrapping_add(1));
+  }
+
+  fn validate_mentions(author: &T::AccountId, mentioned_accounts: &Vec<T::AccountId>) -> dispatch::Result {
+    ensure!(
+      mentioned_accounts.len() <= Self::max_mentions_per_content() as usize,
+      "Too many mentioned accounts"
+    );
+
+    for (i, account) in mentioned_accounts.iter().enumerate() {
+      ensure!(account != author, "Author cannot mention themselves");
+      ensure!(!mentioned_accounts[..i].contains(account), "Mentioned accounts should be unique");
+    }
+
+    Ok(())
+  }
+
+  fn is_muted(account: &T::AccountId, content: &[u8]) -> bool {
+    Self::muted_words_by_account(account)
+      .iter()
+      .any(|word| content.windows(word.len()).any(|window| window == &word[..]))
+  }
+
+  fn filter_out_muting_accounts(accounts: Vec<T::AccountId>, content: &[u8]) -> Vec<T::AccountId> {
+    accounts.into_iter().filter(|account| !Self::is_muted(account, content)).collect()
+  }
+
+  fn add_mentions(mentioned_accounts: Vec<T::AccountId>, mention: Mention<T>) {
+    let max_mentions = Self::max_mentions_per_account() as usize;
+
+    for account in mentioned_accounts {
+      <MentionsByAccount<T>>::mutate(account, |mentions| {
+        // Forget the oldest mention of this account if there are too many of them:
+        if mentions.len() >= max_mentions && !mentions.is_empty() {
+          mentions.remove(0);
+        }
+        mentions.push(mention.clone());
+      });
+    }
+  }
+
+  // Returns numbers of upvotes and downvotes among the given reactions.
+  fn count_votes(reaction_ids: Vec<T::ReactionId>) -> (u32, u32) {
+    let mut upvotes_count = 0;
+    let mut downvotes_count = 0;
+    for reaction in reaction_ids.into_iter().filter_map(|id| Self::reaction_by_id(id)) {
+      match reaction.kind {
+        ReactionKind::Upvote => upvotes_count += 1,
+        ReactionKind::Downvote => downvotes_count += 1,
+        ReactionKind::Custom(_) => {},
+      }
+    }
+    (upvotes_count, downvotes_count)
+  }
+
+  fn ensure_post_reaction_kind_is_valid(post_id: T::PostId, kind: ReactionKind) -> dispatch::Result {
+    if let ReactionKind::Custom(index) = kind {
+      let post = Self::post_by_id(post_id).ok_or("Post was not found by id")?;
+      ensure!(
+        (index as usize) < Self::custom_reactions_by_blog_id(post.blog_id).len(),
+        "Custom reaction was not found in the blog"
+      );
+    }
+    Ok(())
+  }
+
+  fn ensure_comment_reaction_kind_is_valid(comment_id: T::CommentId, kind: ReactionKind) -> dispatch::Result {
+    if let ReactionKind::Custom(_) = kind {
+      let comment = Self::comment_by_id(comment_id).ok_or("Comment was not found by id")?;
+      Self::ensure_post_reaction_kind_is_valid(comment.post_id, kind)?;
+    }
+    Ok(())
+  }
+
+  // Returns false if reaction events are not aggregated, so a caller should emit its own event.
+  // Deltas hold votes only, so a change that involves a custom reaction is never aggregated.
+  fn aggregate_post_reaction_delta(post_id: T::PostId, added: Option<ReactionKind>, removed: Option<ReactionKind>) -> bool {
+    let is_custom = |kind: Option<ReactionKind>| match kind {
+      Some(ReactionKind::Custom(_)) => true,
+      _ => false,
+    };
+    if !Self::aggregate_reaction_events() || is_custom(added) || is_custom(removed) {
+      return false;
+    }
+
+    let votes = |kind: Option<ReactionKind>| match kind {
+      Some(ReactionKind::Upvote) => (1, 0),
+      Some(ReactionKind::Downvote) => (0, 1),
+      _ => (0, 0),
+    };
+    let (upvotes_added, downvotes_added) = votes(added);
+    let (upvotes_removed, downvotes_removed) = votes(removed);
+
+    <PostIdsWithReactionDeltas<T>>::mutate(|ids| {
+      if !ids.contains(&post_id) {
+        ids.push(post_id);
+      }
+    });
+    <ReactionDeltasByPostId<T>>::mutate(post_id, |(upvotes, downvotes)| {
+      *upvotes += upvotes_added - upvotes_removed;
+      *downvotes += downvotes_added - downvotes_removed;
+    });
+    true
+  }
+
+  fn increase_post_reactions_count(post: &mut Post<T>, kind: ReactionKind) {
+    match kind {
+      ReactionKind::Upvote => post.upvotes_count += 1,
+      ReactionKind::Downvote => post.downvotes_count += 1,
+      ReactionKind::Custom(index) => <CustomReactionsCountByPostId<T>>::mutate((post.id, index), |n| *n += 1),
+    }
+  }
+
+  fn decrease_post_reactions_count(post: &mut Post<T>, kind: ReactionKind) {
+    match kind {
+      ReactionKind::Upvote => post.upvotes_count = post.upvotes_count.saturating_sub(1),
+      ReactionKind::Downvote => post.downvotes_count = post.downvotes_count.saturating_sub(1),
+      ReactionKind::Custom(index) => <CustomReactionsCountByPostId<T>>::mutate((post.id, index), |n| *n = n.saturating_sub(1)),
+    }
+  }
+
+  fn increase_comment_reactions_count(comment: &mut Comment<T>, kind: ReactionKind) {
+    match kind {
+      ReactionKind::Upvote => comment.upvotes_count += 1,
+      ReactionKind::Downvote => comment.downvotes_count += 1,
+      ReactionKind::Custom(index) => <CustomReactionsCountByCommentId<T>>::mutate((comment.id, index), |n| *n += 1),
+    }
+  }
+
+  fn decrease_comment_reactions_count(comment: &mut Comment<T>, kind: ReactionKind) {
+    match kind {
+      ReactionKind::Upvote => comment.upvotes_count = comment.upvotes_count.saturating_sub(1),
+      ReactionKind::Downvote => comment.downvotes_count = comment.downvotes_count.saturating_sub(1),
+      ReactionKind::Custom(index) => <CustomReactionsCountByCommentId<T>>::mutate((comment.id, index), |n| *n = n.saturating_sub(1)),
+    }
+  }
+
+  fn update_comments_votes_of_post(post_id: T::PostId, added: Option<ReactionKind>, removed: Option<ReactionKind>) {
+    <PostById<T>>::mutate(post_id, |post_opt| {
+      if let Some(post) = post_opt.as_mut() {
+        match added {
+          Some(ReactionKind::Upvote) => post.comments_upvotes_count += 1,
+          Some(ReactionKind::Downvote) => post.comments_downvotes_count += 1,
+          Some(ReactionKind::Custom(_)) | None => {},
+        }
+        match removed {
+          Some(ReactionKind::Upvote) => post.comments_upvotes_count = post.comments_upvotes_count.saturating_sub(1),
+          Some(ReactionKind::Downvote) => post.comments_downvotes_count = post.comments_downvotes_count.saturating_sub(1),
+          Some(ReactionKind::Custom(_)) | None => {},
+        }
+      }
+    });
+  }
+
+  // Walks up the parent chain of a new comment, starting from its parent.
+  fn increase_replies_total_count(parent_id: Option<T::CommentId>) {
+    let mut ancestor_id = parent_id;
+    let mut depth = 0;
+
+    while let Some(comment_id) = ancestor_id {
+      if depth >= Self::max_reply_depth_to_count() {
+        break;
+      }
+      ancestor_id = None;
+      <CommentById<T>>::mutate(comment_id, |comment_opt| {
+        if let Some(comment) = comment_opt.as_mut() {
+          comment.replies_total_count += 1;
+          ancestor_id = comment.parent_id;
+        }
+      });
+      depth += 1;
+    }
+  }
+
+  fn add_unread_reply(account: T::AccountId, comment_id: T::CommentId, actor: T::AccountId) {
+    let max_replies = Self::max_unread_replies_per_account() as usize;
+    let index = Self::replies_count_by_account(account.clone());
+
+    <UnreadRepliesByAccount<T>>::mutate(account.clone(), |replies| {
+      // Forget the oldest unread reply if there are too many of them:
+      if replies.len() >= max_replies && !replies.is_empty() {
+        replies.remove(0);
+      }
+      replies.push(Reply { index, comment_id, actor });
+    });
+    <RepliesCountByAccount<T>>::insert(account, index + 1);
+  }
+
   fn add_blog_follower(account: T::AccountId, blog_id: T::BlogId) {
     <BlogsFollowedByAccount<T>>::mutate(account.clone(), |ids| ids.push(blog_id));
     <BlogFollowers<T>>::mutate(blog_id, |ids| ids.push(account.clone()));
+    <BlogStatsByBlogId<T>>::mutate(blog_id, |stats| stats.followers_count += 1);
     <BlogFollowedByAccount<T>>::insert((account.clone(), blog_id), true);
     Self::deposit_event(RawEvent::BlogFollowed(account, blog_id));
+
+    Self::check_follower_milestone(blog_id);
+  }
+
+  fn add_comment_to_blog_stats(blog_id: T::BlogId, commenter: &T::AccountId) {
+    <BlogStatsByBlogId<T>>::mutate(blog_id, |stats| stats.comments_count += 1);
+    Self::add_commenter_to_blog(blog_id, commenter);
+  }
+
+  fn add_commenter_to_blog(blog_id: T::BlogId, commenter: &T::AccountId) {
+    if !Self::has_commented_in_blog((blog_id, commenter.clone())) {
+      <HasCommentedInBlog<T>>::insert((blog_id, commenter.clone()), true);
+      <BlogStatsByBlogId<T>>::mutate(blog_id, |stats| stats.commenters_count += 1);
+    }
+  }
+
+  fn remove_topic_follower(account: &T::AccountId, topic: TopicId) {
+    <AccountsFollowingTopic<T>>::mutate(topic, |accounts| {
+      if let Some(index) = accounts.iter().position(|x| x == account) {
+        accounts.swap_remove(index);
+      }
+    });
+  }
+
+  fn remove_blog_follower(account: T::AccountId, blog_id: T::BlogId) {
+    <BlogsFollowedByAccount<T>>::mutate(account.clone(), |blog_ids| {
+      if let Some(index) = blog_ids.iter().position(|x| *x == blog_id) {
+        blog_ids.swap_remove(index);
+      }
+    });
+    <BlogFollowers<T>>::mutate(blog_id, |account_ids| {
+      if let Some(index) = account_ids.iter().position(|x| *x == account) {
+        account_ids.swap_remove(index);
+      }
+    });
+    <BlogStatsByBlogId<T>>::mutate(blog_id, |stats| {
+      stats.followers_count = stats.followers_count.saturating_sub(1);
+    });
+    <BlogFollowedByAccount<T>>::remove((account.clone(), blog_id));
+    Self::deposit_event(RawEvent::BlogUnfollowed(account, blog_id));
+  }
+
+  // Returns how many blogs have been unfollowed.
+  fn unfollow_blogs_batch(account: T::AccountId, limit: u32) -> u32 {
+    let blog_ids: Vec<T::BlogId> = Self::blogs_followed_by_account(account.clone())
+      .into_iter()
+      .take(limit as usize)
+      .collect();
+
+    let unfollowed = blog_ids.len() as u32;
+    for blog_id in blog_ids {
+      Self::remove_blog_follower(account.clone(), blog_id);
+    }
+    unfollowed
+  }
+
+  fn continue_following_resets() {
+    let mut budget = Self::max_unfollows_per_block();
+    let mut queue = Self::following_reset_queue();
+    if queue.is_empty() {
+      return;
+    }
+
+    while budget > 0 && !queue.is_empty() {
+      let account = queue[0].clone();
+      budget -= Self::unfollow_blogs_batch(account.clone(), budget);
+
+      if Self::blogs_followed_by_account(account.clone()).is_empty() {
+        queue.remove(0);
+        Self::deposit_event(RawEvent::FollowingReset(account));
+      }
+    }
+
+    <FollowingResetQueue<T>>::put(queue);
+  }
+
+  // Refunds a fee of an action in a blog from the blog fee pot, if the pot and the account limit allow.
+  // Returns true if anything was refunded.
+  fn refund_sponsored_action(account: &T::AccountId, blog: &Blog<T>) -> bool {
+    let blog_owner = &blog.created.account;
+    if account == blog_owner {
+      return false;
+    }
+
+    let refund = rstd::cmp::min(Self::sponsored_action_refund(), T::TransactionFees::base_fee());
+    let count_key = (blog.id, account.clone());
+    if refund.is_zero() ||
+      Self::fee_pot_by_blog_id(blog.id) < refund ||
+      Self::sponsored_actions_count(&count_key) >= Self::max_sponsored_actions_per_account()
+    {
+      return false;
+    }
+
+    // The returned balance is what could not be moved from the reserve of the owner:
+    let refunded = match T::Currency::repatriate_reserved(blog_owner, account, refund) {
+      Ok(remainder) => refund - remainder,
+      Err(_) => return false,
+    };
+    if refunded.is_zero() {
+      return false;
+    }
+    <FeePotByBlogId<T>>::mutate(blog.id, |pot| *pot -= refunded);
+    <SponsoredActionsCount<T>>::mutate(&count_key, |n| *n += 1);
+    Self::deposit_event(RawEvent::ActionSponsored(account.clone(), blog.id, refunded));
+    true
+  }
+
+  // Unfollowing and following a blog again is not refunded, so that it cannot be repeated to drain the fee pot.
+  fn refund_sponsored_follow(account: &T::AccountId, blog: &Blog<T>) {
+    let key = (blog.id, account.clone());
+    if !Self::has_sponsored_follow(&key) && Self::refund_sponsored_action(account, blog) {
+      <HasSponsoredFollow<T>>::insert(key, true);
+    }
+  }
+
+  // Returns a fee pot of a blog to its owner and stops charging posting and membership fees in the blog.
+  // Pending submissions to the blog are dropped, as no one could approve them, and their deposits are returned.
+  fn release_blog_funds(owner: &T::AccountId, blog_id: T::BlogId) {
+    let pot = <FeePotByBlogId<T>>::take(blog_id);
+    if !pot.is_zero() {
+      T::Currency::unreserve(owner, pot);
+      Self::deposit_event(RawEvent::FeePotWithdrawn(owner.clone(), blog_id, pot));
+    }
+    <PostingFeeByBlogId<T>>::remove(blog_id);
+    <MembershipTiersByBlogId<T>>::remove(blog_id);
+
+    for submission_id in Self::pending_submission_ids_by_blog_id(blog_id) {
+      if let Some(submission) = Self::submission_by_id(submission_id) {
+        T::Currency::unreserve(&submission.created.account, submission.deposit);
+        Self::remove_submission(&submission);
+      }
+    }
+  }
+
+  // Splits off up to a budget of ids from the end of an index of an erased account.
+  fn split_erasure_batch<I>(ids: &mut Vec<I>, budget: &mut u32) -> Vec<I> {
+    let batch = ids.split_off(ids.len().saturating_sub(*budget as usize));
+    *budget -= batch.len() as u32;
+    batch
+  }
+
+  // Removes an erased account from an org. The threshold is lowered if the org could not reach it otherwise.
+  // Pending proposals of the account are dropped, while its approvals stop counting on their own.
+  fn remove_erased_org_member(org_id: u64, account: &T::AccountId) {
+    if let Some(mut org) = Self::org_by_id(org_id) {
+      org.members.retain(|m| m != account);
+      if org.threshold as usize > org.members.len() {
+        org.threshold = rstd::cmp::max(org.members.len(), 1) as u16;
+      }
+      for proposal_id in Self::pending_proposal_ids_by_org_id(org_id) {
+        if let Some(proposal) = Self::org_proposal_by_id(proposal_id) {
+          if proposal.created.account == *account {
+            Self::remove_org_proposal(&proposal);
+          }
+        }
+      }
+      Self::deposit_event(RawEvent::OrgMemberRemoved(org.account.clone(), org_id, account.clone()));
+      <OrgById<T>>::insert(org_id, org);
+    }
+  }
+
+  fn continue_account_erasures() {
+    let mut queue = Self::account_erasure_queue();
+    let tombstone = match Self::tombstone_account() {
+      Some(tombstone) if !queue.is_empty() => tombstone,
+      _ => return,
+    };
+
+    let mut budget = Self::max_erasure_steps_per_block();
+    let account = queue[0].clone();
+
+    if budget > 0 {
+      let mut post_ids = <PostIdsByAccount<T>>::take(account.clone());
+      for post_id in Self::split_erasure_batch(&mut post_ids, &mut budget) {
+        <PostById<T>>::mutate(post_id, |post_opt| {
+          if let Some(post) = post_opt.as_mut() {
+            if post.created.account == account {
+              post.created.account = tombstone.clone();
+            }
+          }
+        });
+        if Self::writer_by_post_id(post_id) == Some(account.clone()) {
+          <WriterByPostId<T>>::insert(post_id, tombstone.clone());
+        }
+      }
+      if !post_ids.is_empty() {
+        <PostIdsByAccount<T>>::insert(account.clone(), post_ids);
+      }
+    }
+
+    if budget > 0 {
+      let mut comment_ids = <CommentIdsByAccount<T>>::take(account.clone());
+      for comment_id in Self::split_erasure_batch(&mut comment_ids, &mut budget) {
+        <CommentById<T>>::mutate(comment_id, |comment_opt| {
+          if let Some(comment) = comment_opt.as_mut() {
+            comment.created.account = tombstone.clone();
+          }
+        });
+        // An open appeal is withdrawn, so that its bond is not left reserved:
+        if let Some(appeal) = Self::appeal_by_comment_id(comment_id) {
+          if appeal.status == AppealStatus::Open {
+            T::Currency::unreserve(&account, appeal.bond);
+            <AppealByCommentId<T>>::remove(comment_id);
+          }
+        }
+      }
+      if !comment_ids.is_empty() {
+        <CommentIdsByAccount<T>>::insert(account.clone(), comment_ids);
+      }
+    }
+
+    if budget > 0 {
+      let mut reaction_ids = <ReactionIdsByAccount<T>>::take(account.clone());
+      for reaction_id in Self::split_erasure_batch(&mut reaction_ids, &mut budget) {
+        <ReactionById<T>>::mutate(reaction_id, |reaction_opt| {
+          if let Some(reaction) = reaction_opt.as_mut() {
+            reaction.created.account = tombstone.clone();
+          }
+        });
+      }
+      if !reaction_ids.is_empty() {
+        <ReactionIdsByAccount<T>>::insert(account.clone(), reaction_ids);
+      }
+    }
+
+    if budget > 0 {
+      let mut series_ids = <SeriesIdsByAccount<T>>::take(account.clone());
+      for series_id in Self::split_erasure_batch(&mut series_ids, &mut budget) {
+        <SeriesById<T>>::mutate(series_id, |series_opt| {
+          if let Some(series) = series_opt.as_mut() {
+            series.created.account = tombstone.clone();
+          }
+        });
+      }
+      if !series_ids.is_empty() {
+        <SeriesIdsByAccount<T>>::insert(account.clone(), series_ids);
+      }
+    }
+
+    if budget > 0 {
+      let mut submission_ids = <PendingSubmissionIdsByAccount<T>>::take(account.clone());
+      for submission_id in Self::split_erasure_batch(&mut submission_ids, &mut budget) {
+        if let Some(submission) = Self::submission_by_id(submission_id) {
+          T::Currency::unreserve(&account, submission.deposit);
+          Self::remove_submission(&submission);
+          Self::deposit_event(RawEvent::SubmissionWithdrawn(account.clone(), submission_id));
+        }
+      }
+      if !submission_ids.is_empty() {
+        <PendingSubmissionIdsByAccount<T>>::insert(account.clone(), submission_ids);
+      }
+    }
+
+    if budget > 0 {
+      let mut blog_ids = <InvitedBlogIdsByAccount<T>>::take(account.clone());
+      for blog_id in Self::split_erasure_batch(&mut blog_ids, &mut budget) {
+        <WriterInviteByBlogAndAccount<T>>::remove((blog_id, account.clone()));
+      }
+      if !blog_ids.is_empty() {
+        <InvitedBlogIdsByAccount<T>>::insert(account.clone(), blog_ids);
+      }
+    }
+
+    if budget > 0 {
+      let mut org_ids = <OrgIdsByMember<T>>::take(account.clone());
+      for org_id in Self::split_erasure_batch(&mut org_ids, &mut budget) {
+        Self::remove_erased_org_member(org_id, &account);
+      }
+      if !org_ids.is_empty() {
+        <OrgIdsByMember<T>>::insert(account.clone(), org_ids);
+      }
+    }
+
+    if budget > 0 {
+      queue.remove(0);
+      Self::deposit_event(RawEvent::AccountErased(account));
+    }
+
+    <AccountErasureQueue<T>>::put(queue);
+  }
+
+  fn check_follower_milestone(blog_id: T::BlogId) {
+    let followers_count = Self::blog_stats_by_blog_id(blog_id).followers_count;
+    let reached = Self::follower_milestone_by_blog_id(blog_id);
+
+    if let Some(milestone) = FOLLOWER_MILESTONES.iter()
+      .find(|m| **m == followers_count && **m > reached)
+    {
+      <FollowerMilestoneByBlogId<T>>::insert(blog_id, *milestone);
+      Self::deposit_event(RawEvent::BlogFollowersMilestoneReached(blog_id, *milestone));
+    }
+  }
+}
+
+// Accounts are mutual followers if each of them follows a blog of the other one.
+impl<T: Trait> IsMutualFollower<T> for Module<T> {
+  fn is_mutual_follower(a: &T::AccountId, b: &T::AccountId) -> bool {
+    Self::follows_blog_of(a, b) && Self::follows_blog_of(b, a)
   }
 }
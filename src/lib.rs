@@ -25,7 +25,9 @@ mod traits;
 use membership::members;
 mod migration;
 mod roles;
-mod blogs;
+pub mod blogs;
+mod pinning;
+mod messages;
 use client::{
     block_builder::api::{self as block_builder_api, CheckInherentsResult, InherentData},
     impl_runtime_apis, runtime_api as client_api,
@@ -126,7 +128,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name: create_runtime_str!("joystream-node"),
     impl_name: create_runtime_str!("joystream-node"),
     authoring_version: 5,
-    spec_version: 2,
+    spec_version: 3,
     impl_version: 0,
     apis: RUNTIME_API_VERSIONS,
 };
@@ -299,6 +301,23 @@ impl blogs::Trait for Runtime {
     type PostId = u64;
     type CommentId = u64;
     type ReactionId = u64;
+    type BlogListId = u64;
+    type TransactionFees = Balances;
+}
+
+impl traits::TransactionBaseFee<Runtime> for Balances {
+    fn base_fee() -> currency::BalanceOf<Runtime> {
+        Balances::transaction_base_fee()
+    }
+}
+
+impl pinning::Trait for Runtime {
+    type Event = Event;
+}
+
+impl messages::Trait for Runtime {
+    type Event = Event;
+    type MutualFollowers = Blogs;
 }
 
 impl migration::Trait for Runtime {
@@ -342,7 +361,9 @@ construct_runtime!(
 		Council: council::{Module, Call, Storage, Event<T>, Config<T>},
 		Memo: memo::{Module, Call, Storage, Event<T>},
 		Members: members::{Module, Call, Storage, Event<T>, Config<T>},
-        Blogs: blogs::{Module, Call, Storage, Event<T>},
+        Blogs: blogs::{Module, Call, Storage, Event<T>, Config<T>},
+		Pinning: pinning::{Module, Call, Storage, Event<T>},
+		Messages: messages::{Module, Call, Storage, Event<T>},
 		Migration: migration::{Module, Call, Storage, Event<T>},
 		Actors: actors::{Module, Call, Storage, Event<T>, Config<T>},
 		DataObjectTypeRegistry: data_object_type_registry::{Module, Call, Storage, Event<T>, Config<T>},
@@ -475,4 +496,22 @@ impl_runtime_apis! {
             Consensus::authorities()
         }
     }
+
+    impl blogs::BlogsApi<Block, AccountId, u64, blogs::PostSummary<Runtime>> for Runtime {
+        fn blog_ids_followed_by(account: AccountId) -> Vec<u64> {
+            Blogs::blog_ids_followed_by(account)
+        }
+
+        fn followers_of_blog(blog_id: u64, offset: u64, limit: u64) -> Vec<AccountId> {
+            Blogs::followers_of_blog(blog_id, offset, limit)
+        }
+
+        fn posts_by_blog(blog_id: u64, offset: u64, limit: u64) -> Vec<blogs::PostSummary<Runtime>> {
+            Blogs::posts_by_blog(blog_id, offset, limit)
+        }
+
+        fn get_feed(account: AccountId, offset: u64, limit: u64) -> Vec<blogs::PostSummary<Runtime>> {
+            Blogs::get_feed(account, offset, limit)
+        }
+    }
 }
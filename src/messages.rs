@@ -0,0 +1,190 @@
+use crate::traits::IsMutualFollower;
+use parity_codec_derive::{Decode, Encode};
+use rstd::prelude::*;
+use runtime_primitives::traits::MaybeDebug;
+use srml_support::{decl_event, decl_module, decl_storage, ensure, StorageMap, StorageValue};
+use system::{self, ensure_signed};
+use timestamp;
+
+const DEFAULT_IPFS_HASH_MAX_LEN: u32 = 100;
+const DEFAULT_MAX_UNANSWERED_MESSAGES: u32 = 3;
+const DEFAULT_MAX_CONVERSATIONS_PER_ACCOUNT: u32 = 1_000;
+
+const MSG_CANNOT_MESSAGE_YOURSELF: &str = "Account cannot send a message to itself";
+const MSG_IPFS_HASH_IS_EMPTY: &str = "Message IPFS hash cannot be empty";
+const MSG_IPFS_HASH_IS_TOO_LONG: &str = "Message IPFS hash is too long";
+const MSG_TOO_MANY_UNANSWERED_MESSAGES: &str =
+    "Too many unanswered messages. Wait until the recipient replies";
+const MSG_SENDER_HAS_TOO_MANY_CONVERSATIONS: &str = "Sender has reached the max number of conversations";
+const MSG_RECIPIENT_HAS_TOO_MANY_CONVERSATIONS: &str = "Recipient has reached the max number of conversations";
+const MSG_NO_SUCH_MESSAGE: &str = "There is no message with this index in the conversation";
+const MSG_ALREADY_READ: &str = "Messages up to this index are already marked as read";
+
+/// Only a reference to an encrypted message is stored on chain,
+/// the message itself lives in IPFS.
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Encode, Decode, Clone, PartialEq)]
+pub struct Message<T: Trait> {
+    pub index: u64,
+    pub sender: T::AccountId,
+    pub recipient: T::AccountId,
+    pub ipfs_hash: Vec<u8>,
+    /// A nonce that was used to seal (encrypt) the message.
+    pub nonce: u64,
+    pub block: T::BlockNumber,
+    pub time: T::Moment,
+}
+
+pub trait Trait: system::Trait + timestamp::Trait + MaybeDebug {
+    type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+
+    /// Mutual followers are not limited by the anti-spam rules.
+    type MutualFollowers: IsMutualFollower<Self>;
+}
+
+/// Both participants of a conversation share the same key, regardless of who sends a message.
+pub type AccountPair<T> = (<T as system::Trait>::AccountId, <T as system::Trait>::AccountId);
+
+decl_storage! {
+    trait Store for Module<T: Trait> as Messages {
+        IpfsHashMaxLen get(ipfs_hash_max_len): u32 = DEFAULT_IPFS_HASH_MAX_LEN;
+
+        /// How many messages an account can send to another one before the latter replies.
+        /// Does not apply to mutual followers.
+        MaxUnansweredMessages get(max_unanswered_messages): u32 = DEFAULT_MAX_UNANSWERED_MESSAGES;
+
+        /// How many conversations an account can have. A new conversation cannot be started
+        /// if either of its participants has reached this limit.
+        MaxConversationsPerAccount get(max_conversations_per_account): u32 = DEFAULT_MAX_CONVERSATIONS_PER_ACCOUNT;
+
+        MessagesByAccountPair get(message_by_account_pair): map (AccountPair<T>, u64) => Option<Message<T>>;
+        MessagesCountByAccountPair get(messages_count_by_account_pair): map AccountPair<T> => u64;
+
+        /// Accounts with whom an account has a conversation.
+        ConversationsByAccount get(conversations_by_account): map T::AccountId => Vec<T::AccountId>;
+
+        /// (reader, counterpart) => number of messages in the conversation that the reader has read.
+        ReadMessagesCount get(read_messages_count): map (T::AccountId, T::AccountId) => u64;
+
+        /// (sender, recipient) => number of messages sent since the recipient's last reply.
+        UnansweredMessagesCount get(unanswered_messages_count): map (T::AccountId, T::AccountId) => u32;
+    }
+}
+
+decl_event! {
+    pub enum Event<T> where
+        <T as system::Trait>::AccountId
+    {
+        /// Params:
+        /// * Sender.
+        /// * Recipient.
+        /// * Index of a message in the conversation.
+        MessageSent(AccountId, AccountId, u64),
+
+        /// Params:
+        /// * Reader.
+        /// * Counterpart in the conversation.
+        /// * Number of messages read.
+        ConversationRead(AccountId, AccountId, u64),
+    }
+}
+
+decl_module! {
+    pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+        fn deposit_event<T>() = default;
+
+        fn send_message(origin, recipient: T::AccountId, ipfs_hash: Vec<u8>, nonce: u64) {
+            let sender = ensure_signed(origin)?;
+
+            ensure!(sender != recipient, MSG_CANNOT_MESSAGE_YOURSELF);
+            ensure!(!ipfs_hash.is_empty(), MSG_IPFS_HASH_IS_EMPTY);
+            ensure!(ipfs_hash.len() <= Self::ipfs_hash_max_len() as usize, MSG_IPFS_HASH_IS_TOO_LONG);
+
+            // Anti-spam: an account cannot flood another one that does not follow it back and does not reply to it.
+            let unanswered_key = (sender.clone(), recipient.clone());
+            ensure!(
+                T::MutualFollowers::is_mutual_follower(&sender, &recipient) ||
+                    Self::unanswered_messages_count(&unanswered_key) < Self::max_unanswered_messages(),
+                MSG_TOO_MANY_UNANSWERED_MESSAGES
+            );
+
+            let pair = Self::account_pair(&sender, &recipient);
+            let index = Self::messages_count_by_account_pair(&pair);
+            if index == 0 {
+                let max_conversations = Self::max_conversations_per_account() as usize;
+                let mut sender_conversations = Self::conversations_by_account(&sender);
+                let mut recipient_conversations = Self::conversations_by_account(&recipient);
+                ensure!(sender_conversations.len() < max_conversations, MSG_SENDER_HAS_TOO_MANY_CONVERSATIONS);
+                ensure!(recipient_conversations.len() < max_conversations, MSG_RECIPIENT_HAS_TOO_MANY_CONVERSATIONS);
+
+                sender_conversations.push(recipient.clone());
+                recipient_conversations.push(sender.clone());
+                <ConversationsByAccount<T>>::insert(sender.clone(), sender_conversations);
+                <ConversationsByAccount<T>>::insert(recipient.clone(), recipient_conversations);
+            }
+
+            let message = Message {
+                index,
+                sender: sender.clone(),
+                recipient: recipient.clone(),
+                ipfs_hash,
+                nonce,
+                block: <system::Module<T>>::block_number(),
+                time: <timestamp::Module<T>>::now(),
+            };
+            <MessagesByAccountPair<T>>::insert((pair.clone(), index), message);
+            <MessagesCountByAccountPair<T>>::insert(&pair, index + 1);
+
+            // A reply resets the counter of messages that the recipient has not answered:
+            <UnansweredMessagesCount<T>>::mutate(&unanswered_key, |count| *count += 1);
+            <UnansweredMessagesCount<T>>::remove((recipient.clone(), sender.clone()));
+
+            // Sender has obviously read the conversation up to their own message:
+            <ReadMessagesCount<T>>::insert((sender.clone(), recipient.clone()), index + 1);
+
+            Self::deposit_event(RawEvent::MessageSent(sender, recipient, index));
+        }
+
+        /// Mark all messages in a conversation up to (including) the given index as read.
+        fn mark_conversation_read(origin, counterpart: T::AccountId, up_to_index: u64) {
+            let reader = ensure_signed(origin)?;
+
+            let pair = Self::account_pair(&reader, &counterpart);
+            ensure!(up_to_index < Self::messages_count_by_account_pair(&pair), MSG_NO_SUCH_MESSAGE);
+
+            let read_key = (reader.clone(), counterpart.clone());
+            let read_count = up_to_index + 1;
+            ensure!(read_count > Self::read_messages_count(&read_key), MSG_ALREADY_READ);
+
+            <ReadMessagesCount<T>>::insert(&read_key, read_count);
+            Self::deposit_event(RawEvent::ConversationRead(reader, counterpart, read_count));
+        }
+
+        fn set_ipfs_hash_max_len(max_len: u32) {
+            <IpfsHashMaxLen<T>>::put(max_len);
+        }
+
+        fn set_max_unanswered_messages(max_messages: u32) {
+            <MaxUnansweredMessages<T>>::put(max_messages);
+        }
+
+        fn set_max_conversations_per_account(max_conversations: u32) {
+            <MaxConversationsPerAccount<T>>::put(max_conversations);
+        }
+    }
+}
+
+impl<T: Trait> Module<T> {
+    pub fn account_pair(a: &T::AccountId, b: &T::AccountId) -> AccountPair<T> {
+        if a < b {
+            (a.clone(), b.clone())
+        } else {
+            (b.clone(), a.clone())
+        }
+    }
+
+    pub fn unread_messages_count(reader: &T::AccountId, counterpart: &T::AccountId) -> u64 {
+        let total = Self::messages_count_by_account_pair(Self::account_pair(reader, counterpart));
+        total.saturating_sub(Self::read_messages_count((reader.clone(), counterpart.clone())))
+    }
+}
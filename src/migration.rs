@@ -1,3 +1,4 @@
+use crate::blogs;
 use crate::membership::members;
 use crate::roles::actors;
 use crate::VERSION;
@@ -10,7 +11,7 @@ use system;
 // the runtime doesn't need to maintain any logic for old migrations. All knowledge about state of the chain and runtime
 // prior to the new runtime taking over is implicit in the migration code implementation. If assumptions are incorrect
 // behaviour is undefined.
-const MIGRATION_FOR_SPEC_VERSION: u32 = 2;
+const MIGRATION_FOR_SPEC_VERSION: u32 = 3;
 
 impl<T: Trait> Module<T> {
     fn runtime_initialization() {
@@ -20,6 +21,27 @@ impl<T: Trait> Module<T> {
 
         print("running runtime initializers");
 
+        // Blog, post and comment counters have been widened from u16 to u32:
+        <blogs::Module<T>>::migrate_to_u32_counters();
+
+        // Reaction ids of a post are not stored in a single vector anymore:
+        <blogs::Module<T>>::migrate_post_reaction_ids();
+
+        // Home feeds are built from the recent posts of every blog:
+        <blogs::Module<T>>::migrate_recent_post_ids();
+
+        // Comments of a post are split into pages:
+        <blogs::Module<T>>::migrate_comment_pages();
+
+        // Blogs keep their totals in one stats record:
+        <blogs::Module<T>>::migrate_blog_stats();
+
+        // Blogs of a writer are indexed by the writer account:
+        <blogs::Module<T>>::migrate_blog_ids_by_writer();
+
+        // Posts, comments and reactions are indexed by their accounts:
+        <blogs::Module<T>>::migrate_content_ids_by_account();
+
         // ...
         // add initialization of other modules introduced in this runtime
         // ...
@@ -31,7 +53,7 @@ impl<T: Trait> Module<T> {
     }
 }
 
-pub trait Trait: system::Trait + members::Trait + actors::Trait {
+pub trait Trait: system::Trait + members::Trait + actors::Trait + blogs::Trait {
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
 }
 
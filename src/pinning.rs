@@ -0,0 +1,310 @@
+use crate::currency::{BalanceOf, GovernanceCurrency};
+use parity_codec_derive::{Decode, Encode};
+use rstd::prelude::*;
+use runtime_primitives::traits::{As, CheckedAdd, CheckedMul, MaybeDebug, Zero};
+use srml_support::traits::{Currency, ReservableCurrency};
+use srml_support::{decl_event, decl_module, decl_storage, dispatch, ensure, StorageMap, StorageValue};
+use system::{self, ensure_signed};
+
+const DEFAULT_CONTENT_HASH_MAX_LEN: u32 = 100;
+const DEFAULT_MAX_PINNERS_PER_CONTENT: u32 = 20;
+const DEFAULT_MIN_PINNER_BOND: u64 = 0;
+const DEFAULT_PINNING_PERIOD: u64 = 14_400; // About a day with 6 sec blocks
+const DEFAULT_CHALLENGE_PERIOD: u64 = 600;
+const DEFAULT_REPORT_BOND: u64 = 10;
+
+const MSG_CONTENT_HASH_IS_EMPTY: &str = "Content hash cannot be empty";
+const MSG_CONTENT_HASH_IS_TOO_LONG: &str = "Content hash is too long";
+const MSG_ALREADY_PINNING: &str = "Account is already pinning this content";
+const MSG_NOT_PINNING: &str = "Account is not pinning this content";
+const MSG_TOO_MANY_PINNERS: &str = "Content has reached the max number of pinners";
+const MSG_BOND_IS_TOO_LOW: &str = "Pinner bond is too low";
+const MSG_BOND_IS_GREATER_THAN_BALANCE: &str = "Balance is too low to be bonded";
+const MSG_NO_PINNERS: &str = "Content has no pinners to pay";
+const MSG_ZERO_PERIODS: &str = "Number of pinning periods should be greater than zero";
+const MSG_CANNOT_PAY_PINNING_FEE: &str = "Balance is too low to pay the pinning fee";
+const MSG_PINNING_FEE_OVERFLOW: &str = "Pinning fee overflows for this number of periods and pinners";
+const MSG_PINNING_FEE_BELOW_EXISTENTIAL_DEPOSIT: &str = "Pinning fee per pinner is below the existential deposit";
+const MSG_PAID_PERIOD_OVERFLOW: &str = "Paid pinning period overflows for this number of periods";
+const MSG_ALREADY_CHALLENGED: &str = "Pinner is already challenged for this content";
+const MSG_CHALLENGE_NOT_FOUND: &str = "There is no challenge for this pinner and content";
+const MSG_CHALLENGE_EXPIRED: &str = "Challenge has expired";
+const MSG_CHALLENGE_NOT_EXPIRED: &str = "Challenge has not expired yet";
+const MSG_CHALLENGED_CANNOT_UNREGISTER: &str = "Challenged pinner cannot unregister until the challenge is resolved";
+
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Encode, Decode, Clone, PartialEq)]
+pub struct Challenge<T: Trait> {
+    pub reporter: T::AccountId,
+    /// Reserved from the reporter until the challenge is resolved.
+    pub bond: BalanceOf<T>,
+    pub reported_at: T::BlockNumber,
+    pub deadline: T::BlockNumber,
+}
+
+pub trait Trait: system::Trait + GovernanceCurrency + MaybeDebug {
+    type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+}
+
+decl_storage! {
+    trait Store for Module<T: Trait> as Pinning {
+        ContentHashMaxLen get(content_hash_max_len): u32 = DEFAULT_CONTENT_HASH_MAX_LEN;
+        MaxPinnersPerContent get(max_pinners_per_content): u32 = DEFAULT_MAX_PINNERS_PER_CONTENT;
+
+        /// Minimum amount of a balance to be bonded by a pinner. Bonding is optional if it is zero.
+        MinPinnerBond get(min_pinner_bond): BalanceOf<T> = BalanceOf::<T>::sa(DEFAULT_MIN_PINNER_BOND);
+
+        /// Length of a pinning period in blocks. Content owners pay for pinning per period.
+        PinningPeriod get(pinning_period): T::BlockNumber = T::BlockNumber::sa(DEFAULT_PINNING_PERIOD);
+
+        /// A fee that every pinner of a content gets per pinning period.
+        PinningFeePerPeriod get(pinning_fee_per_period): BalanceOf<T>;
+
+        /// How many blocks a challenged pinner has to prove that they still pin the content.
+        ChallengePeriod get(challenge_period): T::BlockNumber = T::BlockNumber::sa(DEFAULT_CHALLENGE_PERIOD);
+
+        /// A bond of a reporter of a dead pin. It goes to the pinner if the pinner answers the challenge.
+        ReportBond get(report_bond): BalanceOf<T> = BalanceOf::<T>::sa(DEFAULT_REPORT_BOND);
+
+        PinnersByContent get(pinners_by_content): map Vec<u8> => Vec<T::AccountId>;
+        ContentsByPinner get(contents_by_pinner): map T::AccountId => Vec<Vec<u8>>;
+        PinnerBond get(pinner_bond): map (Vec<u8>, T::AccountId) => BalanceOf<T>;
+
+        /// Up to which block the pinning of a content has been paid.
+        PinningPaidUntil get(pinning_paid_until): map Vec<u8> => T::BlockNumber;
+
+        ChallengeByPinner get(challenge_by_pinner): map (Vec<u8>, T::AccountId) => Option<Challenge<T>>;
+    }
+}
+
+decl_event! {
+    pub enum Event<T> where
+        <T as system::Trait>::AccountId,
+        <T as system::Trait>::BlockNumber,
+        Balance = BalanceOf<T>
+    {
+        PinnerRegistered(AccountId, Vec<u8>, Balance),
+        PinnerUnregistered(AccountId, Vec<u8>),
+
+        /// Params:
+        /// * Payer of a fee.
+        /// * Hash of a pinned content.
+        /// * Total amount paid to all pinners.
+        /// * Block until which the pinning has been paid.
+        PinningFeePaid(AccountId, Vec<u8>, Balance, BlockNumber),
+
+        /// Params:
+        /// * Reporter.
+        /// * Reported pinner.
+        /// * Hash of a content that seems to be not pinned anymore.
+        DeadPinReported(AccountId, AccountId, Vec<u8>),
+        ChallengeAnswered(AccountId, Vec<u8>),
+        PinnerSlashed(AccountId, Vec<u8>, Balance),
+    }
+}
+
+decl_module! {
+    pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+        fn deposit_event<T>() = default;
+
+        /// Register as a pinner of a content, optionally bonding some balance
+        /// that can be slashed if the pin turns out to be dead.
+        fn register_pinner(origin, content_hash: Vec<u8>, bond: BalanceOf<T>) {
+            let pinner = ensure_signed(origin)?;
+
+            Self::ensure_valid_content_hash(&content_hash)?;
+            ensure!(!<PinnerBond<T>>::exists((content_hash.clone(), pinner.clone())), MSG_ALREADY_PINNING);
+            ensure!(
+                Self::pinners_by_content(content_hash.clone()).len() < Self::max_pinners_per_content() as usize,
+                MSG_TOO_MANY_PINNERS
+            );
+            ensure!(bond >= Self::min_pinner_bond(), MSG_BOND_IS_TOO_LOW);
+
+            if !bond.is_zero() {
+                T::Currency::reserve(&pinner, bond)
+                    .map_err(|_| MSG_BOND_IS_GREATER_THAN_BALANCE)?;
+            }
+
+            <PinnersByContent<T>>::mutate(content_hash.clone(), |pinners| pinners.push(pinner.clone()));
+            <ContentsByPinner<T>>::mutate(pinner.clone(), |hashes| hashes.push(content_hash.clone()));
+            <PinnerBond<T>>::insert((content_hash.clone(), pinner.clone()), bond);
+            Self::deposit_event(RawEvent::PinnerRegistered(pinner, content_hash, bond));
+        }
+
+        /// Stop pinning a content and get the bond back.
+        fn unregister_pinner(origin, content_hash: Vec<u8>) {
+            let pinner = ensure_signed(origin)?;
+
+            ensure!(<PinnerBond<T>>::exists((content_hash.clone(), pinner.clone())), MSG_NOT_PINNING);
+            ensure!(
+                !<ChallengeByPinner<T>>::exists((content_hash.clone(), pinner.clone())),
+                MSG_CHALLENGED_CANNOT_UNREGISTER
+            );
+
+            let bond = Self::remove_pinner(&content_hash, &pinner);
+            let _ = T::Currency::unreserve(&pinner, bond);
+            Self::deposit_event(RawEvent::PinnerUnregistered(pinner, content_hash));
+        }
+
+        /// Pay every current pinner of a content the pinning fee for a number of periods.
+        fn pay_pinning_fee(origin, content_hash: Vec<u8>, periods: u32) {
+            let payer = ensure_signed(origin)?;
+
+            ensure!(periods > 0, MSG_ZERO_PERIODS);
+            let pinners = Self::pinners_by_content(content_hash.clone());
+            ensure!(!pinners.is_empty(), MSG_NO_PINNERS);
+
+            let fee_per_pinner = Self::pinning_fee_per_period()
+                .checked_mul(&BalanceOf::<T>::sa(periods as u64))
+                .ok_or(MSG_PINNING_FEE_OVERFLOW)?;
+            let total_fee = fee_per_pinner
+                .checked_mul(&BalanceOf::<T>::sa(pinners.len() as u64))
+                .ok_or(MSG_PINNING_FEE_OVERFLOW)?;
+            // A deposit below the existential deposit would not reach a pinner, while the payer would still be charged:
+            ensure!(
+                fee_per_pinner >= T::Currency::minimum_balance(),
+                MSG_PINNING_FEE_BELOW_EXISTENTIAL_DEPOSIT
+            );
+            ensure!(T::Currency::can_slash(&payer, total_fee), MSG_CANNOT_PAY_PINNING_FEE);
+
+            // Extend a paid period from now if the previous one has already ended:
+            let now = <system::Module<T>>::block_number();
+            let paid_from = rstd::cmp::max(now, Self::pinning_paid_until(content_hash.clone()));
+            let paid_until = Self::pinning_period()
+                .checked_mul(&T::BlockNumber::sa(periods as u64))
+                .and_then(|paid_period| paid_from.checked_add(&paid_period))
+                .ok_or(MSG_PAID_PERIOD_OVERFLOW)?;
+
+            // Take the whole fee at once, so that pinners are either all paid or none of them:
+            let _ = T::Currency::slash(&payer, total_fee);
+            for pinner in pinners.iter() {
+                let _ = T::Currency::deposit_creating(pinner, fee_per_pinner);
+            }
+
+            <PinningPaidUntil<T>>::insert(content_hash.clone(), paid_until);
+
+            Self::deposit_event(RawEvent::PinningFeePaid(payer, content_hash, total_fee, paid_until));
+        }
+
+        /// Report a pinner that seems to not pin a content anymore.
+        /// The pinner should answer the challenge before its deadline or the bond will be slashed.
+        /// The reporter bonds the report bond, which goes to the pinner if the pinner answers in time.
+        fn report_dead_pin(origin, content_hash: Vec<u8>, pinner: T::AccountId) {
+            let reporter = ensure_signed(origin)?;
+
+            ensure!(<PinnerBond<T>>::exists((content_hash.clone(), pinner.clone())), MSG_NOT_PINNING);
+            ensure!(
+                !<ChallengeByPinner<T>>::exists((content_hash.clone(), pinner.clone())),
+                MSG_ALREADY_CHALLENGED
+            );
+
+            let bond = Self::report_bond();
+            if !bond.is_zero() {
+                T::Currency::reserve(&reporter, bond)
+                    .map_err(|_| MSG_BOND_IS_GREATER_THAN_BALANCE)?;
+            }
+
+            let now = <system::Module<T>>::block_number();
+            let challenge = Challenge {
+                reporter: reporter.clone(),
+                bond,
+                reported_at: now,
+                deadline: now + Self::challenge_period(),
+            };
+            <ChallengeByPinner<T>>::insert((content_hash.clone(), pinner.clone()), challenge);
+            Self::deposit_event(RawEvent::DeadPinReported(reporter, pinner, content_hash));
+        }
+
+        /// A challenged pinner confirms that they still pin the content and gets the bond of the reporter.
+        /// A false answer can still be slashed by root with slash_pinner().
+        fn answer_challenge(origin, content_hash: Vec<u8>) {
+            let pinner = ensure_signed(origin)?;
+
+            let challenge = Self::challenge_by_pinner((content_hash.clone(), pinner.clone()))
+                .ok_or(MSG_CHALLENGE_NOT_FOUND)?;
+            ensure!(<system::Module<T>>::block_number() <= challenge.deadline, MSG_CHALLENGE_EXPIRED);
+
+            let _ = T::Currency::repatriate_reserved(&challenge.reporter, &pinner, challenge.bond);
+            <ChallengeByPinner<T>>::remove((content_hash.clone(), pinner.clone()));
+            Self::deposit_event(RawEvent::ChallengeAnswered(pinner, content_hash));
+        }
+
+        /// Anyone can slash a pinner that did not answer a challenge in time.
+        fn slash_unanswered_challenge(origin, content_hash: Vec<u8>, pinner: T::AccountId) {
+            let _ = ensure_signed(origin)?;
+
+            let challenge = Self::challenge_by_pinner((content_hash.clone(), pinner.clone()))
+                .ok_or(MSG_CHALLENGE_NOT_FOUND)?;
+            ensure!(<system::Module<T>>::block_number() > challenge.deadline, MSG_CHALLENGE_NOT_EXPIRED);
+
+            Self::slash_pinner_bond(content_hash, pinner)?;
+        }
+
+        /// Slash a pinner by a decision of root, e.g. after an off-chain investigation of a report.
+        fn slash_pinner(content_hash: Vec<u8>, pinner: T::AccountId) {
+            Self::slash_pinner_bond(content_hash, pinner)?;
+        }
+
+        fn set_min_pinner_bond(bond: BalanceOf<T>) {
+            <MinPinnerBond<T>>::put(bond);
+        }
+
+        fn set_pinning_fee_per_period(fee: BalanceOf<T>) {
+            <PinningFeePerPeriod<T>>::put(fee);
+        }
+
+        fn set_report_bond(bond: BalanceOf<T>) {
+            <ReportBond<T>>::put(bond);
+        }
+    }
+}
+
+impl<T: Trait> Module<T> {
+    pub fn is_pinner(content_hash: &Vec<u8>, account: &T::AccountId) -> bool {
+        <PinnerBond<T>>::exists((content_hash.clone(), account.clone()))
+    }
+
+    fn ensure_valid_content_hash(content_hash: &Vec<u8>) -> dispatch::Result {
+        ensure!(!content_hash.is_empty(), MSG_CONTENT_HASH_IS_EMPTY);
+        ensure!(
+            content_hash.len() <= Self::content_hash_max_len() as usize,
+            MSG_CONTENT_HASH_IS_TOO_LONG
+        );
+        Ok(())
+    }
+
+    fn slash_pinner_bond(content_hash: Vec<u8>, pinner: T::AccountId) -> dispatch::Result {
+        ensure!(Self::is_pinner(&content_hash, &pinner), MSG_NOT_PINNING);
+
+        // A reporter of a dead pin was right, so their bond is returned:
+        if let Some(challenge) = Self::challenge_by_pinner((content_hash.clone(), pinner.clone())) {
+            let _ = T::Currency::unreserve(&challenge.reporter, challenge.bond);
+        }
+
+        let bond = Self::remove_pinner(&content_hash, &pinner);
+        let _ = T::Currency::slash_reserved(&pinner, bond);
+        Self::deposit_event(RawEvent::PinnerSlashed(pinner, content_hash, bond));
+        Ok(())
+    }
+
+    /// Removes a pinner from all indexes and returns the bond of this pinner.
+    fn remove_pinner(content_hash: &Vec<u8>, pinner: &T::AccountId) -> BalanceOf<T> {
+        let key = (content_hash.clone(), pinner.clone());
+        let bond = Self::pinner_bond(&key);
+
+        <PinnerBond<T>>::remove(&key);
+        <ChallengeByPinner<T>>::remove(&key);
+        <PinnersByContent<T>>::mutate(content_hash.clone(), |pinners| {
+            if let Some(index) = pinners.iter().position(|x| x == pinner) {
+                pinners.swap_remove(index);
+            }
+        });
+        <ContentsByPinner<T>>::mutate(pinner.clone(), |hashes| {
+            if let Some(index) = hashes.iter().position(|x| x == content_hash) {
+                hashes.swap_remove(index);
+            }
+        });
+
+        bond
+    }
+}
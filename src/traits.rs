@@ -1,7 +1,8 @@
+use crate::currency::{BalanceOf, GovernanceCurrency};
 use crate::roles::actors;
 use crate::storage::{data_directory, data_object_storage_registry, data_object_type_registry};
 use parity_codec::Codec;
-use runtime_primitives::traits::{As, MaybeSerializeDebug, Member, SimpleArithmetic};
+use runtime_primitives::traits::{As, MaybeSerializeDebug, Member, SimpleArithmetic, Zero};
 use srml_support::Parameter;
 use system;
 
@@ -62,6 +63,29 @@ impl<T: system::Trait> Roles<T> for () {
     }
 }
 
+// Social graph
+pub trait IsMutualFollower<T: system::Trait> {
+    fn is_mutual_follower(a: &T::AccountId, b: &T::AccountId) -> bool;
+}
+
+impl<T: system::Trait> IsMutualFollower<T> for () {
+    fn is_mutual_follower(_a: &T::AccountId, _b: &T::AccountId) -> bool {
+        false
+    }
+}
+
+// Fees
+pub trait TransactionBaseFee<T: GovernanceCurrency> {
+    // The part of a transaction fee that every signed transaction pays, whatever its length.
+    fn base_fee() -> BalanceOf<T>;
+}
+
+impl<T: GovernanceCurrency> TransactionBaseFee<T> for () {
+    fn base_fee() -> BalanceOf<T> {
+        Zero::zero()
+    }
+}
+
 // Storage
 pub trait IsActiveDataObjectType<T: data_object_type_registry::Trait> {
     fn is_active_data_object_type(_which: &T::DataObjectTypeId) -> bool;